@@ -0,0 +1,27 @@
+use c6::*;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn bench_load(c: &mut Criterion) {
+    let mut board = Board::new_square(99);
+    for y in -49..=49 {
+        for x in -49..=49 {
+            board.set(Point::new(x, y), board.turn().stone).unwrap();
+        }
+    }
+    let mut record = Vec::new();
+    board.save_record(&mut record).unwrap();
+    let mut position = Vec::new();
+    board.save_position(&mut position).unwrap();
+
+    let mut group = c.benchmark_group("load_dense");
+    group.bench_function("record", |b| {
+        b.iter(|| Board::load_record(&record[..]).unwrap())
+    });
+    group.bench_function("position", |b| {
+        b.iter(|| Board::load_position(&position[..]).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_load);
+criterion_main!(benches);
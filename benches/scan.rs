@@ -0,0 +1,33 @@
+use c6::*;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+const LEN: i32 = 4096;
+
+fn bench_scan(c: &mut Criterion) {
+    let mut board = Board::new_infinite();
+    for x in (0..LEN).step_by(3) {
+        board.set(Point::new(x, 0), Stone::Black).unwrap();
+    }
+
+    let mut group = c.benchmark_group("scan_line");
+    group.bench_function("get", |b| {
+        b.iter(|| {
+            (0..LEN)
+                .filter(|&x| board.get(black_box(Point::new(x, 0))).is_some())
+                .count()
+        })
+    });
+    group.bench_function("line_through", |b| {
+        b.iter(|| {
+            board
+                .line_through(black_box(Point::ORIGIN), Direction::Horizontal)
+                .take(LEN as usize)
+                .filter(|(_, slot)| slot.is_some())
+                .count()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_scan);
+criterion_main!(benches);
@@ -0,0 +1,39 @@
+use c6::*;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+use common::Lcg;
+
+// Plays a game of the given moves, evaluating the position after each.
+fn play_and_score(moves: &[Point], tracked: bool) -> i64 {
+    let mut board = Board::new_infinite();
+    if tracked {
+        board.enable_threat_tracking();
+    }
+    let weights = ScoreWeights::default();
+    let mut total = 0;
+    for &point in moves {
+        if board.set(point, board.turn().stone).is_ok() {
+            total += board.score(Stone::Black, &weights);
+            total += board.candidate_moves().len() as i64;
+        }
+    }
+    total
+}
+
+fn bench_threats(c: &mut Criterion) {
+    let mut rng = Lcg::new(1);
+    let moves: Vec<Point> = (0..300)
+        .map(|_| Point::new(rng.below(40) as i32, rng.below(40) as i32))
+        .collect();
+    assert_eq!(play_and_score(&moves, true), play_and_score(&moves, false));
+
+    let mut group = c.benchmark_group("score_game");
+    group.bench_function("rescan", |b| b.iter(|| play_and_score(&moves, false)));
+    group.bench_function("tracked", |b| b.iter(|| play_and_score(&moves, true)));
+    group.finish();
+}
+
+criterion_group!(benches, bench_threats);
+criterion_main!(benches);
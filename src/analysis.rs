@@ -0,0 +1,324 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::{sort_points, Board, Bounds, Direction, Point, Stone, Turn};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Win {
+        stone: Stone,
+        line: [Point; 6],
+    },
+    Draw,
+    /// The given stone resigned.
+    Resign(Stone),
+}
+
+/// The lengths of line that win the game, which vary between rule sets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum WinRule {
+    /// A line of six or more stones wins.
+    #[default]
+    SixOrMore,
+    /// Only a line of exactly six stones wins, and longer lines (overlines)
+    /// don't count.
+    ExactlySix,
+}
+
+impl WinRule {
+    fn wins(self, len: usize) -> bool {
+        match self {
+            WinRule::SixOrMore => len >= 6,
+            WinRule::ExactlySix => len == 6,
+        }
+    }
+}
+
+/// The weights of the windows counted by [`Board::count_patterns`], used by
+/// [`Board::score`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScoreWeights {
+    /// The weight of a window holding `i + 1` stones of a color and no
+    /// stones of the other color.
+    ///
+    /// By default, each stone in a window multiplies its weight by 4 up to
+    /// three stones. Windows with four or five stones can be completed in a
+    /// single turn, so they weigh much more, and a window of six is a win.
+    pub by_count: [i64; 6],
+}
+
+impl Default for ScoreWeights {
+    fn default() -> ScoreWeights {
+        ScoreWeights {
+            by_count: [1, 4, 16, 256, 256, 1 << 20],
+        }
+    }
+}
+
+/// A violation of the order of turns, found by [`Board::validate_opening`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum OpeningError {
+    /// The move continues a turn that should have ended.
+    #[error("move {index}: turn of {stone:?} is too long")]
+    TurnTooLong { index: usize, stone: Stone },
+    /// The move starts a new turn before the current one is over.
+    #[error("move {index}: turn of {stone:?} is too short")]
+    TurnTooShort { index: usize, stone: Stone },
+}
+
+// Counts the consecutive stones of a color along a line, skipping its start.
+fn run_length(line: impl Iterator<Item = (Point, Option<Stone>)>, stone: Stone) -> usize {
+    line.skip(1)
+        .take_while(|&(_, slot)| slot == Some(stone))
+        .count()
+}
+
+// Finds the empty point right after the run of a color along a line,
+// skipping its start.
+fn run_end(line: impl Iterator<Item = (Point, Option<Stone>)>, stone: Stone) -> Option<Point> {
+    let (point, slot) = line.skip(1).find(|&(_, slot)| slot != Some(stone))?;
+    slot.is_none().then_some(point)
+}
+
+impl Board {
+    /// Checks whether the stone at `point` is part of a winning line under
+    /// the board's [`WinRule`], returning the first six points of the line
+    /// if so.
+    pub fn check_win(&self, point: Point) -> Option<[Point; 6]> {
+        let stone = self.get(point)?;
+        for dir in Direction::ALL {
+            let back = run_length(self.line_through_rev(point, dir), stone);
+            let forth = run_length(self.line_through(point, dir), stone);
+            if self.rule.wins(back + 1 + forth) {
+                let (start, _) = self.line_through_rev(point, dir).nth(back)?;
+                let mut line = self.line_through(start, dir).map(|(p, _)| p);
+                return Some([(); 6].map(|_| line.next().unwrap()));
+            }
+        }
+        None
+    }
+
+    /// Resigns the game on behalf of the given stone.
+    ///
+    /// The resignation is withdrawn if a stone is placed afterwards, or if
+    /// the board moves through the history.
+    pub fn resign(&mut self, stone: Stone) {
+        self.resigned = Some(stone);
+    }
+
+    /// Returns the outcome of the game, or `None` if it is not over yet.
+    ///
+    /// A bounded board filled up without a win is a draw. Under
+    /// [`WinRule::ExactlySix`], a game won by an exact six stays won even if
+    /// the line is extended afterwards, as with [`Board::cached_outcome`].
+    pub fn outcome(&self) -> Option<Outcome> {
+        for (point, stone) in self.stones() {
+            if let Some(line) = self.check_win(point) {
+                return Some(Outcome::Win { stone, line });
+            }
+        }
+        self.cached_outcome()
+    }
+
+    fn outcome_without_win(&self) -> Option<Outcome> {
+        if let Some(stone) = self.resigned {
+            return Some(Outcome::Resign(stone));
+        }
+        let size = match self.bounds {
+            Bounds::Infinite => return None,
+            Bounds::Rect(width, height) => width as u128 * height as u128,
+            Bounds::Limited(max) => (max as u128 * 2 + 1).pow(2),
+        };
+        let (black, white) = self.count_by_color();
+        if (black + white) as u128 >= size {
+            return Some(Outcome::Draw);
+        }
+        None
+    }
+
+    /// Returns the outcome of the game like [`Board::outcome`], but with the
+    /// win found as the moves are made instead of by scanning the board.
+    ///
+    /// This is the first win in the order of the moves, so if there are
+    /// several lines of six, the line reported may differ from `outcome`.
+    pub fn cached_outcome(&self) -> Option<Outcome> {
+        match self.win {
+            Some((_, outcome)) => Some(outcome),
+            None => self.outcome_without_win(),
+        }
+    }
+
+    /// Checks whether the game is over, as cheaply as [`Board::cached_outcome`].
+    pub fn is_terminal(&self) -> bool {
+        self.cached_outcome().is_some()
+    }
+
+    /// Checks that the moves in the past record take turns by the rules:
+    /// black places one stone first, and then each side places two stones
+    /// in turn, unless passing. The first stone may be white if the sides
+    /// were swapped by [`Board::swap_sides`].
+    ///
+    /// Turns set explicitly with [`Board::set_turn`] are not taken into
+    /// account, so this catches records that don't follow the usual order.
+    pub fn validate_opening(&self) -> Result<(), OpeningError> {
+        let mut turn = Turn {
+            stone: Stone::Black,
+            remaining: 1,
+        };
+        for (index, &(point, stone)) in self.past_record().iter().enumerate() {
+            // The opening stone turns white if the sides are swapped.
+            if index == 0 && self.is_swapped() {
+                turn = Turn {
+                    stone: Stone::Black,
+                    remaining: 2,
+                };
+                continue;
+            }
+            if stone != turn.stone {
+                return Err(if turn.remaining == 2 {
+                    OpeningError::TurnTooLong { index, stone }
+                } else {
+                    OpeningError::TurnTooShort {
+                        index,
+                        stone: turn.stone,
+                    }
+                });
+            }
+            turn = if point == Point::PASS {
+                Turn {
+                    stone: stone.opposite(),
+                    remaining: 2,
+                }
+            } else {
+                turn.after(stone)
+            };
+        }
+        Ok(())
+    }
+
+    /// Returns the empty points where placing a stone of the given color
+    /// would complete a winning line, sorted by index.
+    pub fn winning_spots(&self, stone: Stone) -> Vec<Point> {
+        let mut spots = Vec::new();
+        for (point, _) in self.stones().filter(|&(_, s)| s == stone) {
+            for dir in Direction::ALL {
+                spots.extend(run_end(self.line_through(point, dir), stone));
+                spots.extend(run_end(self.line_through_rev(point, dir), stone));
+            }
+        }
+        sort_points(&mut spots);
+        spots.dedup();
+
+        spots.retain(|&spot| {
+            Direction::ALL.into_iter().any(|dir| {
+                let len = run_length(self.line_through(spot, dir), stone)
+                    + 1
+                    + run_length(self.line_through_rev(spot, dir), stone);
+                self.rule.wins(len)
+            })
+        });
+        spots
+    }
+
+    /// Returns the length of the longest line of the given color on the
+    /// board, along with its two ends, or `(0, None)` if there are no stones
+    /// of that color.
+    ///
+    /// Each line is only counted from its start, and the first longest line
+    /// found in the order of the moves is returned.
+    pub fn longest_line(&self, stone: Stone) -> (u32, Option<[Point; 2]>) {
+        let mut longest = (0, None);
+        for (point, _) in self.stones().filter(|&(_, s)| s == stone) {
+            for dir in Direction::ALL {
+                if run_length(self.line_through_rev(point, dir), stone) > 0 {
+                    continue;
+                }
+                let len = run_length(self.line_through(point, dir), stone);
+                if len as u32 + 1 > longest.0 {
+                    let (end, _) = self.line_through(point, dir).nth(len).unwrap();
+                    longest = (len as u32 + 1, Some([point, end]));
+                }
+            }
+        }
+        longest
+    }
+
+    /// Counts the windows of six points in a line, within bounds, that hold
+    /// stones of the given color and none of the other, which are the places
+    /// where the color could still make a six. The count at index `i` is of
+    /// windows holding `i + 1` stones.
+    ///
+    /// This takes no scan while [`Board::enable_threat_tracking`] is on.
+    pub fn count_patterns(&self, stone: Stone) -> [u32; 6] {
+        if let Some(counts) = self.tracked_patterns(stone) {
+            return counts;
+        }
+        let mut windows = HashSet::new();
+        for (point, _) in self.stones().filter(|&(_, s)| s == stone) {
+            for dir in Direction::ALL {
+                let (dx, dy) = dir.delta();
+                for i in 0..6 {
+                    windows.insert((point - Point::new(dx * i, dy * i), dir));
+                }
+            }
+        }
+
+        let mut counts = [0; 6];
+        for (start, dir) in windows {
+            if let Some(n @ 1..) = self.window_count(start, dir, stone) {
+                counts[n - 1] += 1;
+            }
+        }
+        counts
+    }
+
+    // Counts the stones of a color in the window of six points from `start`,
+    // returning `None` if the window is out of bounds or blocked.
+    fn window_count(&self, start: Point, dir: Direction, stone: Stone) -> Option<usize> {
+        let (dx, dy) = dir.delta();
+        let mut n = 0;
+        for i in 0..6 {
+            let point = start + Point::new(dx * i, dy * i);
+            if !self.bounds.contains(point) {
+                return None;
+            }
+            match self.get(point) {
+                Some(s) if s == stone => n += 1,
+                Some(_) => return None,
+                None => {}
+            }
+        }
+        Some(n)
+    }
+
+    /// Evaluates the position for the given color, as the weighted sum of
+    /// its [`Board::count_patterns`] minus that of the other color.
+    pub fn score(&self, stone: Stone, weights: &ScoreWeights) -> i64 {
+        let sum = |stone| {
+            self.count_patterns(stone)
+                .into_iter()
+                .zip(weights.by_count)
+                .map(|(count, weight)| count as i64 * weight)
+                .sum::<i64>()
+        };
+        sum(stone) - sum(stone.opposite())
+    }
+
+    /// Counts the stones on the board in `cell` by `cell` squares, keyed by
+    /// the coordinates of each square, i.e. the stone coordinates divided by
+    /// `cell` and rounded down. Squares without stones are left out.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cell` is zero.
+    pub fn density_map(&self, cell: u32) -> BTreeMap<(i32, i32), u32> {
+        assert!(cell > 0, "cell size must be positive");
+        let cell = i64::from(cell);
+        let mut map = BTreeMap::new();
+        for (point, _) in self.stones() {
+            let x = i64::from(point.x).div_euclid(cell) as i32;
+            let y = i64::from(point.y).div_euclid(cell) as i32;
+            *map.entry((x, y)).or_insert(0) += 1;
+        }
+        map
+    }
+}
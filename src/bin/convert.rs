@@ -1,51 +1,89 @@
 use std::{
+    env,
     error::Error,
     fs::{self, File},
-    io::{BufRead, BufReader, BufWriter},
-    path::Path,
+    io::{BufReader, BufWriter},
+    path::{Path, PathBuf},
+    process,
 };
 
 use c6::*;
 
-fn parse_point(s: &str) -> Point {
-    let x = (s.as_bytes()[0] - b'A') as i32 - 9;
-    let y = s[1..].parse::<i32>().unwrap() - 1 - 9;
-    Point { x, y }
+// The offset of the center from the top-left corner on a 19 by 19 board.
+const DEFAULT_OFFSET: u8 = 9;
+
+fn convert(
+    input: &Path,
+    output_dir: &Path,
+    offset: u8,
+    auto_first: bool,
+) -> Result<usize, Box<dyn Error>> {
+    let file = File::open(input)?;
+    let options = ServerLogOptions { offset, auto_first };
+    let board = Board::from_server_log_with(BufReader::new(file), &options)?;
+
+    let file_name = input.file_name().ok_or("input has no file name")?;
+    let path = output_dir.join(file_name).with_extension("c6");
+    let bw = BufWriter::new(File::create(path)?);
+    board.save_record(bw)?;
+    Ok(board.total_count())
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
-    for entry in fs::read_dir("../connect6/records")? {
-        let entry = entry?;
-        let file = File::open(entry.path())?;
+    let mut args: Vec<String> = env::args().collect();
+    let len = args.len();
+    args.retain(|arg| arg != "--no-auto-first");
+    let auto_first = args.len() == len;
+    if !(3..=4).contains(&args.len()) {
+        eprintln!(
+            "usage: {} [--no-auto-first] <input> <output_dir> [offset]",
+            args[0]
+        );
+        eprintln!();
+        eprintln!("<input> is a log file or a directory of them.");
+        eprintln!("[offset] is the distance from the corner to the center of the board,");
+        eprintln!("which defaults to {DEFAULT_OFFSET} for a 19x19 board.");
+        eprintln!();
+        eprintln!("The opening black stone is placed at the center, unless the log");
+        eprintln!("starts with a single stone, or --no-auto-first is given.");
+        process::exit(2);
+    }
 
-        let mut board = Board::new_square(19);
-        board.set(Point::ORIGIN, Stone::Black)?;
+    let input = Path::new(&args[1]);
+    let output_dir = Path::new(&args[2]);
+    let offset = match args.get(3) {
+        Some(offset) => offset
+            .parse()
+            .map_err(|_| "offset must be a number from 0 to 12")?,
+        None => DEFAULT_OFFSET,
+    };
+    // Columns are single letters.
+    if !(0..=12).contains(&offset) {
+        return Err("offset must be a number from 0 to 12".into());
+    }
+
+    let inputs: Vec<PathBuf> = if input.is_dir() {
+        let mut paths = fs::read_dir(input)?
+            .map(|entry| Ok(entry?.path()))
+            .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+        paths.sort();
+        paths
+    } else {
+        vec![input.into()]
+    };
+    fs::create_dir_all(output_dir)?;
 
-        for line in BufReader::new(file).lines() {
-            let line = line?;
-            if !line.contains("moved") {
-                continue;
+    let mut converted = 0;
+    for path in &inputs {
+        match convert(path, output_dir, offset, auto_first) {
+            Ok(count) => {
+                println!("{}: ok, {count} moves", path.display());
+                converted += 1;
             }
-            let stone = if line.contains("Black") {
-                Stone::Black
-            } else {
-                Stone::White
-            };
-            let s = line.split_once('(').unwrap().1;
-            let s = s.strip_suffix(')').unwrap();
-            let (a, b) = s.split_once(", ").unwrap();
-            let (a, b) = (parse_point(a), parse_point(b));
-
-            board.set(a, stone)?;
-            board.set(b, stone)?;
+            Err(err) => println!("{}: {err}", path.display()),
         }
-
-        let path = Path::new("records")
-            .join(entry.file_name())
-            .with_extension("c6");
-        let bw = BufWriter::new(File::create(path)?);
-        board.save_record(bw)?;
     }
+    println!("converted {converted} of {} files", inputs.len());
 
     Ok(())
 }
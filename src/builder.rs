@@ -0,0 +1,113 @@
+use crate::{Board, Bounds, Point, Stone};
+
+/// A coordinate accepted by [`BoardBuilder`]: either a [`Point`] or a point
+/// in algebraic notation, as parsed by [`Point::from_algebraic`].
+pub trait IntoPoint {
+    /// Converts into a point, panicking if the notation is invalid.
+    fn into_point(self) -> Point;
+}
+
+impl IntoPoint for Point {
+    fn into_point(self) -> Point {
+        self
+    }
+}
+
+impl IntoPoint for &str {
+    fn into_point(self) -> Point {
+        Point::from_algebraic(self)
+            .unwrap_or_else(|| panic!("invalid algebraic notation: {self:?}"))
+    }
+}
+
+/// A builder for setting up positions by playing moves in turn.
+///
+/// Each move must be made by the side whose turn it is, so the builder
+/// follows the usual order: black places one stone first, and then each
+/// side places two stones in turn. Illegal moves cause a panic.
+///
+/// ```
+/// use c6::{BoardBuilder, Point, Stone};
+///
+/// let board = BoardBuilder::square(19)
+///     .black("J10")
+///     .white_pair("K10", "K11")
+///     .black_pair(Point::new(-1, -1), Point::new(-2, -2))
+///     .pass()
+///     .build();
+///
+/// assert_eq!(board.total_count(), 6);
+/// assert_eq!(board.get(Point::ORIGIN), Some(Stone::Black));
+/// assert_eq!(board.turn().stone, Stone::Black);
+/// ```
+#[derive(Debug)]
+pub struct BoardBuilder {
+    board: Board,
+}
+
+impl BoardBuilder {
+    pub const fn new(bounds: Bounds) -> BoardBuilder {
+        BoardBuilder {
+            board: Board::new(bounds),
+        }
+    }
+
+    pub const fn infinite() -> BoardBuilder {
+        BoardBuilder::new(Bounds::Infinite)
+    }
+
+    pub const fn square(size: u32) -> BoardBuilder {
+        BoardBuilder::new(Bounds::Rect(size, size))
+    }
+
+    /// Places a stone of the given color.
+    ///
+    /// # Panics
+    ///
+    /// Panics if it is not the turn of `stone`, or if the point is invalid,
+    /// occupied or out of bounds.
+    pub fn stone(mut self, point: impl IntoPoint, stone: Stone) -> BoardBuilder {
+        let point = point.into_point();
+        let index = self.board.index();
+        let turn = self.board.turn();
+        assert!(
+            turn.stone == stone,
+            "move {index}: {stone} played {point} on the turn of {}",
+            turn.stone
+        );
+        if let Err(err) = self.board.set(point, stone) {
+            panic!("move {index}: {stone} cannot play {point}: {err}");
+        }
+        self
+    }
+
+    /// Places a black stone.
+    pub fn black(self, point: impl IntoPoint) -> BoardBuilder {
+        self.stone(point, Stone::Black)
+    }
+
+    /// Places a white stone.
+    pub fn white(self, point: impl IntoPoint) -> BoardBuilder {
+        self.stone(point, Stone::White)
+    }
+
+    /// Places two black stones.
+    pub fn black_pair(self, a: impl IntoPoint, b: impl IntoPoint) -> BoardBuilder {
+        self.black(a).black(b)
+    }
+
+    /// Places two white stones.
+    pub fn white_pair(self, a: impl IntoPoint, b: impl IntoPoint) -> BoardBuilder {
+        self.white(a).white(b)
+    }
+
+    /// Passes the rest of the current turn.
+    pub fn pass(mut self) -> BoardBuilder {
+        self.board.pass();
+        self
+    }
+
+    pub fn build(self) -> Board {
+        self.board
+    }
+}
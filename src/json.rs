@@ -0,0 +1,117 @@
+use serde_json::{json, Map, Value};
+
+use crate::{
+    record::{format_bounds, parse_bounds, parse_result, parse_rule, parse_turn},
+    Board, Point, SetError, Stone, Turn, WinRule,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum JsonError {
+    #[error("malformed json: {0}")]
+    Syntax(#[from] serde_json::Error),
+    #[error("invalid field: {0}")]
+    Field(&'static str),
+    #[error("move {index} at {point}: unable to set on board: {source}")]
+    Set {
+        index: usize,
+        point: Point,
+        source: SetError,
+    },
+}
+
+impl Board {
+    /// Serializes the past record as JSON, with the same fields as the
+    /// headers of a record:
+    ///
+    /// ```json
+    /// {"bounds":"Rect(19*19)","moves":[[0,0,"Black"],[1,0,"White"]]}
+    /// ```
+    ///
+    /// The `turn`, `result`, `rule` and `opening` fields are added when set,
+    /// as in `"turn":"White(1)"`, `"result":"Resign(Black)"`,
+    /// `"rule":"ExactlySix"` and `"opening":"Swapped"`. Passes are at
+    /// `(i32::MIN, i32::MIN)`, as in [`Board::to_move_list`].
+    pub fn to_json(&self) -> String {
+        let moves: Vec<Value> = self
+            .to_move_list()
+            .into_iter()
+            .map(|(x, y, stone)| json!([x, y, stone.to_string()]))
+            .collect();
+
+        let mut obj = Map::new();
+        obj.insert("bounds".into(), format_bounds(self.bounds).into());
+        if let Some(Turn { stone, remaining }) = self.turn {
+            obj.insert("turn".into(), format!("{stone}({remaining})").into());
+        }
+        if let Some(stone) = self.resigned {
+            obj.insert("result".into(), format!("Resign({stone})").into());
+        }
+        if self.rule == WinRule::ExactlySix {
+            obj.insert("rule".into(), "ExactlySix".into());
+        }
+        if self.is_swapped() {
+            obj.insert("opening".into(), "Swapped".into());
+        }
+        obj.insert("moves".into(), moves.into());
+        Value::Object(obj).to_string()
+    }
+
+    /// Deserializes a board from the JSON written by [`Board::to_json`],
+    /// replaying the moves.
+    pub fn from_json(s: &str) -> Result<Board, JsonError> {
+        use JsonError::*;
+
+        let value: Value = serde_json::from_str(s)?;
+        let field = |key| value.get(key).map(|v| v.as_str().ok_or(Field(key)));
+
+        let bounds = field("bounds").ok_or(Field("bounds"))??;
+        let bounds = parse_bounds(bounds).ok_or(Field("bounds"))?;
+        let mut board = Board::new(bounds);
+        if let Some(rule) = field("rule") {
+            board.set_win_rule(parse_rule(rule?).ok_or(Field("rule"))?);
+        }
+
+        let moves = value
+            .get("moves")
+            .and_then(Value::as_array)
+            .ok_or(Field("moves"))?;
+        for (index, mv) in moves.iter().enumerate() {
+            let (x, y, stone) = parse_move(mv).ok_or(Field("moves"))?;
+            let point = Point::new(x, y);
+            if point == Point::PASS {
+                board.push_pass(stone);
+            } else {
+                board.set(point, stone).map_err(|source| Set {
+                    index,
+                    point,
+                    source,
+                })?;
+            }
+        }
+
+        if let Some(opening) = field("opening") {
+            if opening? != "Swapped" || !board.mark_swapped() {
+                return Err(Field("opening"));
+            }
+        }
+        if let Some(turn) = field("turn") {
+            let turn = parse_turn(turn?).ok_or(Field("turn"))?;
+            board.set_turn(turn).expect("parsed turns should be valid");
+        }
+        if let Some(result) = field("result") {
+            board.resign(parse_result(result?).ok_or(Field("result"))?);
+        }
+        Ok(board)
+    }
+}
+
+fn parse_move(value: &Value) -> Option<(i32, i32, Stone)> {
+    match value.as_array()?.as_slice() {
+        [x, y, stone] => Some((
+            x.as_i64()?.try_into().ok()?,
+            y.as_i64()?.try_into().ok()?,
+            stone.as_str()?.parse().ok()?,
+        )),
+        _ => None,
+    }
+}
@@ -1,7 +1,45 @@
+mod analysis;
+mod builder;
+#[cfg(feature = "json")]
+mod json;
+mod notation;
+mod play;
+mod position;
+#[cfg(feature = "rand")]
+mod random;
 mod record;
-pub use record::LoadRecordError;
+mod server_log;
+mod svg;
+mod threats;
+#[cfg(feature = "wasm")]
+mod wasm;
+pub use analysis::{OpeningError, Outcome, ScoreWeights, WinRule};
+pub use builder::{BoardBuilder, IntoPoint};
+#[cfg(feature = "json")]
+pub use json::JsonError;
+#[cfg(feature = "rand")]
+pub use play::RandomMoveGen;
+pub use play::{run_game, GreedyMoveGen, MoveGen};
+pub use position::LoadPositionError;
+pub use record::{
+    LoadOptions, LoadRecordError, ParsePositionError, RecordHeader, RecordSummary, SaveOptions,
+};
+pub use server_log::ServerLogOptions;
+pub use svg::SvgSizeError;
+#[cfg(feature = "wasm")]
+pub use wasm::WasmBoard;
 
-use std::collections::BTreeMap;
+use std::{
+    collections::BTreeMap,
+    fmt,
+    hash::{Hash, Hasher},
+    iter,
+    ops::{Add, Index, IndexMut, Mul, Sub},
+    str::FromStr,
+    time::Duration,
+};
+
+use threats::ThreatMap;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Point {
@@ -12,6 +50,11 @@ pub struct Point {
 impl Point {
     pub const ORIGIN: Point = Point::new(0, 0);
 
+    /// The point reserved for marking passes in the record, which has the
+    /// largest index. It is out of any bounds, even infinite ones, so no
+    /// stone can be placed here.
+    pub const PASS: Point = Point::new(i32::MIN, i32::MIN);
+
     pub const fn new(x: i32, y: i32) -> Point {
         Point { x, y }
     }
@@ -26,6 +69,267 @@ impl Point {
         let (x, y) = deinterleave(i);
         Point::new(zigzag_decode(x), zigzag_decode(y))
     }
+
+    /// Parses a point in the algebraic notation of a 19 by 19 board, where
+    /// columns `A` to `S` go left to right and rows `1` to `19` go top to
+    /// bottom, so that `J10` is the origin.
+    pub fn from_algebraic(s: &str) -> Option<Point> {
+        if s.is_empty() || !s.is_char_boundary(1) {
+            return None;
+        }
+        let (col, row) = s.split_at(1);
+        let col = col.as_bytes()[0].to_ascii_uppercase();
+        if !(b'A'..=b'S').contains(&col) {
+            return None;
+        }
+        match row.parse::<i32>() {
+            Ok(row @ 1..=19) => Some(Point::new((col - b'A') as i32 - 9, row - 10)),
+            _ => None,
+        }
+    }
+
+    /// Formats the point in algebraic notation, if it is on a 19 by 19 board.
+    ///
+    /// See [`Point::from_algebraic`] for the notation.
+    pub fn to_algebraic(self) -> Option<String> {
+        if !Bounds::Rect(19, 19).contains(self) {
+            return None;
+        }
+        let col = (b'A' + (self.x + 9) as u8) as char;
+        Some(format!("{col}{}", self.y + 10))
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("expected a point like (x, y)")]
+pub struct ParsePointError;
+
+impl FromStr for Point {
+    type Err = ParsePointError;
+
+    /// Parses a point in the form `(x, y)`, where the parentheses are optional.
+    fn from_str(s: &str) -> Result<Point, ParsePointError> {
+        let s = s.trim();
+        let s = match s.strip_prefix('(') {
+            Some(s) => s.strip_suffix(')').ok_or(ParsePointError)?,
+            None => s,
+        };
+        let (x, y) = s.split_once(',').ok_or(ParsePointError)?;
+        match (x.trim().parse(), y.trim().parse()) {
+            (Ok(x), Ok(y)) => Ok(Point::new(x, y)),
+            _ => Err(ParsePointError),
+        }
+    }
+}
+
+// Point arithmetic wraps around on overflow, so that walking off one end
+// of the coordinate space continues from the other end, which is exactly
+// how the zigzag-encoded indexes treat the infinite board.
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Point {
+        Point::new(self.x.wrapping_add(rhs.x), self.y.wrapping_add(rhs.y))
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Point {
+        Point::new(self.x.wrapping_sub(rhs.x), self.y.wrapping_sub(rhs.y))
+    }
+}
+
+impl Mul<i32> for Point {
+    type Output = Point;
+
+    fn mul(self, rhs: i32) -> Point {
+        Point::new(self.x.wrapping_mul(rhs), self.y.wrapping_mul(rhs))
+    }
+}
+
+/// The four axes of a line on the board, with y going down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Horizontal,
+    Vertical,
+    DiagDown,
+    DiagUp,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Horizontal,
+        Direction::Vertical,
+        Direction::DiagDown,
+        Direction::DiagUp,
+    ];
+
+    pub fn delta(self) -> (i32, i32) {
+        match self {
+            Direction::Horizontal => (1, 0),
+            Direction::Vertical => (0, 1),
+            Direction::DiagDown => (1, 1),
+            Direction::DiagUp => (1, -1),
+        }
+    }
+}
+
+/// The eight symmetries of a square, with y going down so that rotations
+/// are clockwise on screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Symmetry {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    /// Reflection across the vertical axis.
+    FlipHorizontal,
+    /// Reflection across the horizontal axis.
+    FlipVertical,
+    /// Reflection across the diagonal going down.
+    Transpose,
+    /// Reflection across the diagonal going up.
+    AntiTranspose,
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 8] = [
+        Symmetry::Identity,
+        Symmetry::Rotate90,
+        Symmetry::Rotate180,
+        Symmetry::Rotate270,
+        Symmetry::FlipHorizontal,
+        Symmetry::FlipVertical,
+        Symmetry::Transpose,
+        Symmetry::AntiTranspose,
+    ];
+
+    fn apply(self, x: i64, y: i64) -> (i64, i64) {
+        match self {
+            Symmetry::Identity => (x, y),
+            Symmetry::Rotate90 => (-y, x),
+            Symmetry::Rotate180 => (-x, -y),
+            Symmetry::Rotate270 => (y, -x),
+            Symmetry::FlipHorizontal => (-x, y),
+            Symmetry::FlipVertical => (x, -y),
+            Symmetry::Transpose => (y, x),
+            Symmetry::AntiTranspose => (-y, -x),
+        }
+    }
+
+    // Whether the symmetry maps the x-axis onto the y-axis.
+    fn swaps_axes(self) -> bool {
+        matches!(
+            self,
+            Symmetry::Rotate90
+                | Symmetry::Rotate270
+                | Symmetry::Transpose
+                | Symmetry::AntiTranspose
+        )
+    }
+}
+
+impl Point {
+    /// Transforms the point by a symmetry about the origin, wrapping around
+    /// on overflow like the arithmetic operators.
+    pub fn transform(self, sym: Symmetry) -> Point {
+        let (x, y) = sym.apply(self.x as i64, self.y as i64);
+        Point::new(x as i32, y as i32)
+    }
+
+    /// Returns the four orthogonally adjacent points, wrapping around on
+    /// overflow like the arithmetic operators.
+    pub fn neighbors(self) -> [Point; 4] {
+        [(0, -1), (-1, 0), (1, 0), (0, 1)].map(|(dx, dy)| self + Point::new(dx, dy))
+    }
+
+    /// Returns the eight adjacent points, diagonals included, in rows from
+    /// top to bottom. Wraps around on overflow like [`Point::neighbors`].
+    pub fn neighbors8(self) -> [Point; 8] {
+        [
+            (-1, -1),
+            (0, -1),
+            (1, -1),
+            (-1, 0),
+            (1, 0),
+            (-1, 1),
+            (0, 1),
+            (1, 1),
+        ]
+        .map(|(dx, dy)| self + Point::new(dx, dy))
+    }
+
+    /// Iterates over the points within a Chebyshev distance of `r`, which
+    /// form a square of side `2r + 1` centered here, in rows from top to
+    /// bottom. Points beyond the range of `i32` are skipped.
+    pub fn disk(self, r: u32) -> impl Iterator<Item = Point> {
+        self.square(r, |_| 1)
+    }
+
+    /// Iterates over the points at a Chebyshev distance of exactly `r`,
+    /// which form the edge of the square of [`Point::disk`], in rows from top
+    /// to bottom. Points beyond the range of `i32` are skipped.
+    pub fn ring(self, r: u32) -> impl Iterator<Item = Point> {
+        // Rows other than the top and bottom ones only have their two ends.
+        self.square(r, move |dy| {
+            if dy.unsigned_abs() == r as u64 {
+                1
+            } else {
+                2 * r as usize
+            }
+        })
+    }
+
+    // Iterates over the rows of the square of side `2r + 1` centered here,
+    // stepping through each row by `step(dy)` from the left end.
+    fn square(self, r: u32, step: impl Fn(i64) -> usize) -> impl Iterator<Item = Point> {
+        let r = r as i64;
+        let (x, y) = (self.x as i64, self.y as i64);
+        let in_range = |c: &i64| i32::try_from(*c).is_ok();
+        (y - r..=y + r).filter(in_range).flat_map(move |py| {
+            (x - r..=x + r)
+                .step_by(step(py - y))
+                .filter(in_range)
+                .map(move |px| Point::new(px as i32, py as i32))
+        })
+    }
+
+    /// Checks whether the point lies on the line through `a` and `b`, which
+    /// must run along one of the four axes of [`Direction`].
+    ///
+    /// If `a` and `b` are the same point, any of the four lines through it
+    /// counts. Returns `false` if `a` and `b` are not aligned on an axis.
+    pub fn in_line_with(self, a: Point, b: Point) -> bool {
+        let delta = |p: Point| (p.x as i64 - a.x as i64, p.y as i64 - a.y as i64);
+        let aligned = |(dx, dy): (i64, i64)| dx == 0 || dy == 0 || dx.abs() == dy.abs();
+
+        let (sx, sy) = delta(self);
+        let (dx, dy) = delta(b);
+        if a == b {
+            return aligned((sx, sy));
+        }
+        aligned((dx, dy)) && sx * dy.signum() == sy * dx.signum()
+    }
+}
+
+/// Sorts points by [`Point::index`], which keeps nearby points mostly
+/// together, as they are stored on the board.
+pub fn sort_points(points: &mut [Point]) {
+    points.sort_unstable_by_key(|p| p.index());
+}
+
+/// Sorts moves by the index of their points like [`sort_points`], keeping
+/// moves at the same point in their original order.
+pub fn sort_moves(moves: &mut [(Point, Stone)]) {
+    moves.sort_by_key(|(p, _)| p.index());
 }
 
 fn zigzag_encode(x: i32) -> u32 {
@@ -36,6 +340,33 @@ fn zigzag_decode(x: u32) -> i32 {
     ((x >> 1) ^ (x & 1).wrapping_neg()) as i32
 }
 
+// Returns the range of chunk coordinates along an axis whose chunks hold
+// the coordinates from `lo` to `hi`, inclusive.
+fn chunk_span(lo: i32, hi: i32) -> (u32, u32) {
+    let (z_min, z_max) = if lo >= 0 {
+        (zigzag_encode(lo), zigzag_encode(hi))
+    } else if hi < 0 {
+        (zigzag_encode(hi), zigzag_encode(lo))
+    } else {
+        (0, zigzag_encode(lo).max(zigzag_encode(hi)))
+    };
+    (z_min >> CHUNK_SIZE_BITS, z_max >> CHUNK_SIZE_BITS)
+}
+
+// Returns the range of coordinates whose zigzag encodings are below `n`.
+fn zigzag_range(n: u32) -> (i32, i32) {
+    (-((n / 2) as i32), (n.saturating_sub(1) / 2) as i32)
+}
+
+// Returns the range of coordinates of magnitude at most `max`.
+fn limited_range(max: u32) -> (i32, i32) {
+    let max = max as i64;
+    (
+        (-max).max(i32::MIN as i64) as i32,
+        max.min(i32::MAX as i64) as i32,
+    )
+}
+
 #[cfg(not(target_arch = "x86_64"))]
 fn interleave(x: u32, y: u32) -> u64 {
     fn scatter_even(x: u32) -> u64 {
@@ -83,7 +414,7 @@ fn deinterleave(i: u64) -> (u32, u32) {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Stone {
     Black = 0,
     White = 1,
@@ -98,14 +429,73 @@ impl Stone {
     }
 }
 
-const CHUNK_SIZE_BITS: u32 = 4;
-const CHUNK_SIZE: usize = 1 << CHUNK_SIZE_BITS;
-const WORDS_PER_CHUNK: usize = CHUNK_SIZE * CHUNK_SIZE * 2 / 64;
+impl fmt::Display for Stone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Stone::Black => "Black",
+            Stone::White => "White",
+        })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("expected black or white")]
+pub struct ParseStoneError;
+
+impl FromStr for Stone {
+    type Err = ParseStoneError;
+
+    /// Parses a stone from its name or initial in any case, or from the
+    /// symbols `●` and `○`.
+    fn from_str(s: &str) -> Result<Stone, ParseStoneError> {
+        match s.to_ascii_lowercase().as_str() {
+            "black" | "b" | "●" => Ok(Stone::Black),
+            "white" | "w" | "○" => Ok(Stone::White),
+            _ => Err(ParseStoneError),
+        }
+    }
+}
+
+/// A value for each color, indexed by [`Stone`], as in `counts[Stone::Black]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ByStone<T>([T; 2]);
+
+impl<T> ByStone<T> {
+    pub const fn new(black: T, white: T) -> ByStone<T> {
+        ByStone([black, white])
+    }
+
+    /// Iterates over the values along with their colors, black first.
+    pub fn iter(&self) -> impl Iterator<Item = (Stone, &T)> {
+        [Stone::Black, Stone::White].into_iter().zip(&self.0)
+    }
+}
+
+impl<T> Index<Stone> for ByStone<T> {
+    type Output = T;
+
+    fn index(&self, stone: Stone) -> &T {
+        &self.0[stone as usize]
+    }
+}
+
+impl<T> IndexMut<Stone> for ByStone<T> {
+    fn index_mut(&mut self, stone: Stone) -> &mut T {
+        &mut self.0[stone as usize]
+    }
+}
 
-const SLOT_INDEX_BITS: u32 = 5;
-const WORD_INDEX_BITS: u32 = CHUNK_SIZE_BITS * 2 - SLOT_INDEX_BITS;
+// A chunk covers `CHUNK_SIZE` by `CHUNK_SIZE` zigzag-encoded coordinates,
+// with each point taking 2 bits of a word. The index of a point splits
+// into the chunk index, the word index and the slot index, from high to low.
+pub const CHUNK_SIZE_BITS: u32 = 4;
+pub const CHUNK_SIZE: usize = 1 << CHUNK_SIZE_BITS;
+pub const WORDS_PER_CHUNK: usize = CHUNK_SIZE * CHUNK_SIZE * 2 / 64;
 
-#[derive(Debug, Default)]
+pub const SLOT_INDEX_BITS: u32 = 5;
+pub const WORD_INDEX_BITS: u32 = CHUNK_SIZE_BITS * 2 - SLOT_INDEX_BITS;
+
+#[derive(Debug, Clone, Default)]
 struct Chunk {
     words: [u64; WORDS_PER_CHUNK],
 }
@@ -138,6 +528,21 @@ impl Chunk {
             _ => Some(Stone::White),
         }
     }
+
+    fn invert_colors(&mut self) {
+        for word in &mut self.words {
+            *word = (*word & 0x5555555555555555) << 1 | (*word >> 1) & 0x5555555555555555;
+        }
+    }
+
+    fn count_by_color(&self) -> (usize, usize) {
+        self.words.iter().fold((0, 0), |(black, white), &word| {
+            (
+                black + (word & 0x5555555555555555).count_ones() as usize,
+                white + (word & 0xaaaaaaaaaaaaaaaa).count_ones() as usize,
+            )
+        })
+    }
 }
 
 fn extract_lo_bits(i: &mut u64, bits: u32) -> u64 {
@@ -155,7 +560,7 @@ impl Point {
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct RawBoard {
     // Visualization:
     // 3 2 2 3
@@ -178,6 +583,34 @@ impl RawBoard {
             .and_then(|chunk| chunk.get(word_i, slot_i))
     }
 
+    /// Gets the slots at many points at once, in the order of the points.
+    ///
+    /// The queries are sorted by chunk internally, so that each chunk is
+    /// looked up only once.
+    pub fn get_many(&self, points: &[Point]) -> Vec<Option<Stone>> {
+        let mut queries: Vec<_> = points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (p.indexes(), i))
+            .collect();
+        queries.sort_unstable_by_key(|&((chunk_i, _, _), _)| chunk_i);
+
+        let mut slots = vec![None; points.len()];
+        let mut last = None;
+        for ((chunk_i, word_i, slot_i), i) in queries {
+            let chunk = match last {
+                Some((last_i, chunk)) if last_i == chunk_i => chunk,
+                _ => {
+                    let chunk = self.chunk(chunk_i);
+                    last = Some((chunk_i, chunk));
+                    chunk
+                }
+            };
+            slots[i] = chunk.and_then(|chunk| chunk.get(word_i, slot_i));
+        }
+        slots
+    }
+
     #[must_use]
     pub fn set(&mut self, point: Point, stone: Stone) -> bool {
         let (chunk_i, word_i, slot_i) = point.indexes();
@@ -189,6 +622,220 @@ impl RawBoard {
         self.chunk_mut(chunk_i).unset(word_i, slot_i)
     }
 
+    /// Turns every black stone white and every white stone black.
+    pub fn invert_colors(&mut self) {
+        self.chunks.values_mut().for_each(Chunk::invert_colors);
+    }
+
+    pub fn count_by_color(&self) -> (usize, usize) {
+        self.chunks
+            .values()
+            .map(Chunk::count_by_color)
+            .fold((0, 0), |(black, white), (b, w)| (black + b, white + w))
+    }
+
+    /// Iterates over the occupancy masks of the nonempty blocks of 64
+    /// points, as `(block_i, black, white)` in increasing order of `block_i`.
+    ///
+    /// A point with index `i` is in the block `i >> 6`, at bit `i & 63` of
+    /// the masks. Since the index interleaves the zigzag-encoded coordinates
+    /// with x in the even bits, the bits of a mask follow the Morton order of
+    /// an 8 by 8 square of zigzag-encoded coordinates, and each chunk is made
+    /// up of four consecutive blocks.
+    pub fn bitboards(&self) -> impl Iterator<Item = (u64, u64, u64)> + '_ {
+        self.chunks.iter().flat_map(|(&chunk_i, chunk)| {
+            chunk
+                .words
+                .chunks(2)
+                .enumerate()
+                .filter_map(move |(i, words)| {
+                    // Even bits of a word are for black and odd bits for white.
+                    let (black_lo, white_lo) = deinterleave(words[0]);
+                    let (black_hi, white_hi) = deinterleave(words[1]);
+                    let black = (black_hi as u64) << 32 | black_lo as u64;
+                    let white = (white_hi as u64) << 32 | white_lo as u64;
+                    let block_i = chunk_i << 2 | i as u64;
+                    (black | white != 0).then_some((block_i, black, white))
+                })
+        })
+    }
+
+    /// Counts the stones of the given color, or of both colors if `stone` is
+    /// `None`, in the rectangle of `size` with its top-left corner at
+    /// `top_left`. Points past the edges of the coordinate space are left out.
+    ///
+    /// Only the chunks overlapping the rectangle are visited, and their words
+    /// are masked and counted as a whole instead of point by point.
+    pub fn count_in(&self, top_left: Point, size: (u32, u32), stone: Option<Stone>) -> usize {
+        const LO_BITS: u64 = 0x5555555555555555;
+        let (width, height) = size;
+        if width == 0 || height == 0 {
+            return 0;
+        }
+        let end =
+            |start: i32, len: u32| (start as i64 + len as i64 - 1).min(i32::MAX as i64) as i32;
+        let (x_lo, x_hi) = (top_left.x, end(top_left.x, width));
+        let (y_lo, y_hi) = (top_left.y, end(top_left.y, height));
+
+        // The local coordinates along an axis in chunk `c` that lie within
+        // the rectangle, as a bitmask.
+        let axis_mask = |c: u32, lo: i32, hi: i32| {
+            (0..CHUNK_SIZE as u32)
+                .filter(|&l| (lo..=hi).contains(&zigzag_decode(c << CHUNK_SIZE_BITS | l)))
+                .fold(0u32, |mask, l| mask | 1 << l)
+        };
+        let count_chunk = |chunk_i: u64, chunk: &Chunk| {
+            let (cx, cy) = deinterleave(chunk_i);
+            let (x_mask, y_mask) = (axis_mask(cx, x_lo, x_hi), axis_mask(cy, y_lo, y_hi));
+            if x_mask == 0 || y_mask == 0 {
+                return 0;
+            }
+            let full = (1 << CHUNK_SIZE) - 1;
+            let mut count = 0;
+            for (word_i, &word) in chunk.words.iter().enumerate() {
+                let mut mask = !0;
+                if x_mask != full || y_mask != full {
+                    mask = 0;
+                    for slot_i in 0..64 / 2 {
+                        let (lx, ly) = deinterleave((word_i as u64) << SLOT_INDEX_BITS | slot_i);
+                        if x_mask >> lx & y_mask >> ly & 1 != 0 {
+                            mask |= 3 << (slot_i * 2);
+                        }
+                    }
+                }
+                let bits = match stone {
+                    None => (word | word >> 1) & LO_BITS,
+                    Some(Stone::Black) => word & LO_BITS,
+                    Some(Stone::White) => word & !LO_BITS,
+                };
+                count += (bits & mask).count_ones() as usize;
+            }
+            count
+        };
+
+        let (cx_min, cx_max) = chunk_span(x_lo, x_hi);
+        let (cy_min, cy_max) = chunk_span(y_lo, y_hi);
+        let spanned = (cx_max - cx_min + 1) as u64 * (cy_max - cy_min + 1) as u64;
+        if spanned < self.chunks.len() as u64 {
+            let mut count = 0;
+            for cy in cy_min..=cy_max {
+                for cx in cx_min..=cx_max {
+                    let chunk_i = interleave(cx, cy);
+                    if let Some(chunk) = self.chunk(chunk_i) {
+                        count += count_chunk(chunk_i, chunk);
+                    }
+                }
+            }
+            count
+        } else {
+            self.chunks
+                .iter()
+                .map(|(&chunk_i, chunk)| count_chunk(chunk_i, chunk))
+                .sum()
+        }
+    }
+
+    /// Returns the rectangles that the chunk of the given index covers, as
+    /// pairs of top-left and bottom-right corners, both inclusive.
+    ///
+    /// A chunk is contiguous in the zigzag-encoded coordinates, so apart from
+    /// the ones on the axes it covers four separate rectangles, mirroring
+    /// each other across the axes.
+    pub fn chunk_rects(chunk_i: u64) -> Vec<(Point, Point)> {
+        fn ranges(c: u32) -> Vec<(i32, i32)> {
+            let half = CHUNK_SIZE as i64 / 2;
+            let lo = c as i64 * half;
+            if c == 0 {
+                vec![(-half as i32, (half - 1) as i32)]
+            } else {
+                vec![
+                    ((-lo - half) as i32, (-lo - 1) as i32),
+                    (lo as i32, (lo + half - 1) as i32),
+                ]
+            }
+        }
+
+        let (cx, cy) = deinterleave(chunk_i);
+        let mut rects = Vec::new();
+        for (y_min, y_max) in ranges(cy) {
+            for (x_min, x_max) in ranges(cx) {
+                rects.push((Point::new(x_min, y_min), Point::new(x_max, y_max)));
+            }
+        }
+        rects
+    }
+
+    /// Returns a reader that caches the last chunk it looked up, which
+    /// speeds up runs of reads that fall within the same chunk.
+    pub fn reader(&self) -> RawBoardReader<'_> {
+        RawBoardReader {
+            board: self,
+            last: None,
+        }
+    }
+
+    /// Iterates over the words of the chunks in increasing order of their
+    /// indexes, as stored. A chunk may be all zeros if its stones have been
+    /// removed.
+    ///
+    /// Each slot of a word takes 2 bits, the lower one set for black and the
+    /// higher one for white. See [`RawBoard::bitboards`] for the order of
+    /// the slots.
+    pub fn iter_chunks(&self) -> impl Iterator<Item = (u64, &[u64; WORDS_PER_CHUNK])> + '_ {
+        self.chunks
+            .iter()
+            .map(|(&chunk_i, chunk)| (chunk_i, &chunk.words))
+    }
+
+    /// Rebuilds a raw board from the chunks yielded by
+    /// [`RawBoard::iter_chunks`], where a later chunk replaces an earlier
+    /// one of the same index.
+    ///
+    /// No slot should have both of its bits set, or it reads as white.
+    pub fn from_chunks(
+        chunks: impl IntoIterator<Item = (u64, [u64; WORDS_PER_CHUNK])>,
+    ) -> RawBoard {
+        RawBoard {
+            chunks: chunks
+                .into_iter()
+                .map(|(chunk_i, words)| (chunk_i, Chunk { words }))
+                .collect(),
+        }
+    }
+
+    /// Copies all stones from `other` onto this board, word by word.
+    ///
+    /// Stones already on this board at the same points must be of the same
+    /// color. Otherwise, the board is left unchanged and the conflicting
+    /// point with the smallest index is returned.
+    pub fn merge(&mut self, other: &RawBoard) -> Result<(), Point> {
+        const LO_BITS: u64 = 0x5555555555555555;
+        let occupied = |word: u64| (word | word >> 1) & LO_BITS;
+
+        for (&chunk_i, chunk) in &other.chunks {
+            let Some(own) = self.chunk(chunk_i) else {
+                continue;
+            };
+            for (word_i, (&a, &b)) in own.words.iter().zip(&chunk.words).enumerate() {
+                let conflicts = occupied(a) & occupied(b) & occupied(a ^ b);
+                if conflicts != 0 {
+                    let slot_i = conflicts.trailing_zeros() as u64 / 2;
+                    let i =
+                        (chunk_i << WORD_INDEX_BITS | word_i as u64) << SLOT_INDEX_BITS | slot_i;
+                    return Err(Point::from_index(i));
+                }
+            }
+        }
+
+        for (&chunk_i, chunk) in &other.chunks {
+            let own = self.chunk_mut(chunk_i);
+            for (a, b) in own.words.iter_mut().zip(chunk.words) {
+                *a |= b;
+            }
+        }
+        Ok(())
+    }
+
     fn chunk(&self, chunk_i: u64) -> Option<&Chunk> {
         self.chunks.get(&chunk_i)
     }
@@ -198,18 +845,173 @@ impl RawBoard {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+// The reader borrows the board, so the cache can't outlive a mutation that
+// might insert a chunk and move the others around in the tree.
+#[derive(Debug, Clone)]
+pub struct RawBoardReader<'a> {
+    board: &'a RawBoard,
+    last: Option<(u64, Option<&'a Chunk>)>,
+}
+
+impl<'a> RawBoardReader<'a> {
+    pub fn get(&mut self, point: Point) -> Option<Stone> {
+        let (chunk_i, word_i, slot_i) = point.indexes();
+        let chunk = match self.last {
+            Some((last_i, chunk)) if last_i == chunk_i => chunk,
+            _ => {
+                let chunk = self.board.chunk(chunk_i);
+                self.last = Some((chunk_i, chunk));
+                chunk
+            }
+        };
+        chunk.and_then(|chunk| chunk.get(word_i, slot_i))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum Bounds {
     #[default]
     Infinite,
     Rect(u32, u32),
+    /// An infinite board with coordinates of at most the given magnitude,
+    /// guarding against stones placed far away by pathological input.
+    Limited(u32),
 }
 
 impl Bounds {
+    /// Checks whether a point is within bounds, which is never the case for
+    /// [`Point::PASS`].
     pub fn contains(self, p: Point) -> bool {
+        if p == Point::PASS {
+            return false;
+        }
         match self {
             Bounds::Infinite => true,
             Bounds::Rect(x, y) => zigzag_encode(p.x) < x && zigzag_encode(p.y) < y,
+            Bounds::Limited(max) => p.x.unsigned_abs() <= max && p.y.unsigned_abs() <= max,
+        }
+    }
+
+    /// Returns the point within bounds nearest to `p`, moving it along each
+    /// axis independently.
+    ///
+    /// An empty `Rect` contains no points, so `p` is clamped to the origin
+    /// along its empty axes, which is still out of bounds.
+    pub fn clamp_point(self, p: Point) -> Point {
+        match self {
+            Bounds::Infinite => p,
+            Bounds::Rect(width, height) => {
+                let (x_min, x_max) = zigzag_range(width);
+                let (y_min, y_max) = zigzag_range(height);
+                Point::new(p.x.clamp(x_min, x_max), p.y.clamp(y_min, y_max))
+            }
+            Bounds::Limited(max) => {
+                let (min, max) = limited_range(max);
+                Point::new(p.x.clamp(min, max), p.y.clamp(min, max))
+            }
+        }
+    }
+
+    /// Tells whether `p` is in a corner, on an edge or in the interior of
+    /// the bounds, or outside them.
+    ///
+    /// Every point is in the interior of an infinite board.
+    pub fn classify(self, p: Point) -> CellKind {
+        if !self.contains(p) {
+            return CellKind::Outside;
+        }
+        let (on_x_edge, on_y_edge) = match self {
+            Bounds::Infinite => (false, false),
+            Bounds::Rect(width, height) => {
+                let (x_min, x_max) = zigzag_range(width);
+                let (y_min, y_max) = zigzag_range(height);
+                (p.x == x_min || p.x == x_max, p.y == y_min || p.y == y_max)
+            }
+            Bounds::Limited(max) => (p.x.unsigned_abs() == max, p.y.unsigned_abs() == max),
+        };
+        match (on_x_edge, on_y_edge) {
+            (true, true) => CellKind::Corner,
+            (false, false) => CellKind::Interior,
+            _ => CellKind::Edge,
+        }
+    }
+}
+
+/// Where a point lies relative to some bounds, as told by
+/// [`Bounds::classify`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CellKind {
+    Corner,
+    Edge,
+    Interior,
+    Outside,
+}
+
+/// Returns the star points of a bounded board, such as the corner 4-4
+/// points and the center of a 19 by 19 board.
+///
+/// An infinite board has infinitely many star points, so none are returned
+/// here. Use [`is_star_point`] to test for them instead.
+pub fn star_points(bounds: Bounds) -> Vec<Point> {
+    let Bounds::Rect(width, height) = bounds else {
+        return Vec::new();
+    };
+
+    fn axis(n: u32) -> Vec<i32> {
+        let Some((low, high)) = star_lines(n) else {
+            return Vec::new();
+        };
+        if n % 2 == 1 {
+            vec![low, 0, high]
+        } else {
+            vec![low, high]
+        }
+    }
+
+    let (xs, ys) = (axis(width), axis(height));
+    let mut points = Vec::new();
+    for &y in &ys {
+        for &x in &xs {
+            let point = Point::new(x, y);
+            if star_side_allowed(width, height, point) {
+                points.push(point);
+            }
+        }
+    }
+    points
+}
+
+// The outer star lines along an axis of `n` points, if it is long enough to
+// have any. An odd axis also has one through its center.
+fn star_lines(n: u32) -> Option<(i32, i32)> {
+    let edge = match n {
+        13.. => 3,
+        7..=12 => 2,
+        _ => return None,
+    };
+    let (min, max) = zigzag_range(n);
+    Some((min + edge, max - edge))
+}
+
+// Star points on the sides are only traditional on large boards.
+fn star_side_allowed(width: u32, height: u32, point: Point) -> bool {
+    let on_side = (point.x == 0) != (point.y == 0);
+    !on_side || width.min(height) >= 15
+}
+
+/// Checks whether a point is a star point, where an infinite board has them
+/// on a lattice of every sixth row and column through the origin.
+pub fn is_star_point(bounds: Bounds, point: Point) -> bool {
+    match bounds {
+        Bounds::Infinite | Bounds::Limited(_) => point.x % 6 == 0 && point.y % 6 == 0,
+        Bounds::Rect(width, height) => {
+            let on_axis = |n: u32, c: i32| match star_lines(n) {
+                Some((low, high)) => c == low || c == high || (n % 2 == 1 && c == 0),
+                None => false,
+            };
+            on_axis(width, point.x)
+                && on_axis(height, point.y)
+                && star_side_allowed(width, height, point)
         }
     }
 }
@@ -222,12 +1024,106 @@ pub enum SetError {
     OutOfBounds,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, thiserror::Error)]
+#[error("cannot jump to move {index}: only moves {floor} to {len} are in the history")]
+pub struct JumpError {
+    pub index: usize,
+    pub len: usize,
+    /// The undo floor, which is the earliest move that can be jumped to.
+    pub floor: usize,
+}
+
+/// An error from [`Board::restore`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum RestoreError {
+    /// Moves before the snapshot have been undone since it was taken.
+    #[error("snapshot is ahead of the board")]
+    Ahead,
+    /// The snapshot is below the undo floor.
+    #[error("snapshot is below the undo floor")]
+    BelowFloor,
+    /// The move before the snapshot differs, so it was taken of another
+    /// board or of moves replaced since.
+    #[error("snapshot was taken of another history")]
+    Mismatch,
+}
+
+/// An error from [`Board::set_turn`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("a turn has 1 or 2 stones remaining, not {remaining}")]
+pub struct SetTurnError {
+    pub remaining: u8,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("cannot jump to turn {turn} of a record of {len} turns")]
+pub struct JumpToTurnError {
+    pub turn: usize,
+    pub len: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Turn {
+    pub stone: Stone,
+    pub remaining: u8,
+}
+
+impl Turn {
+    fn after(self, stone: Stone) -> Turn {
+        if stone == self.stone && self.remaining > 1 {
+            Turn {
+                stone,
+                remaining: self.remaining - 1,
+            }
+        } else {
+            Turn {
+                stone: stone.opposite(),
+                remaining: 2,
+            }
+        }
+    }
+}
+
+/// The state of a board at some point, taken by [`Board::snapshot`].
+///
+/// This holds no stones, so it is cheap to take.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Snapshot {
+    index: usize,
+    // The last move before the snapshot, for telling apart histories.
+    last: Option<(Point, Stone)>,
+    turn: Option<Turn>,
+    resigned: Option<Stone>,
+    occupied: Option<(Point, Point)>,
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct Board {
     board: RawBoard,
     bounds: Bounds,
     record: Vec<(Point, Stone)>,
+    // The time taken by each move in the record, if known.
+    times: Vec<Option<Duration>>,
     index: usize,
+    turn: Option<Turn>,
+    resigned: Option<Stone>,
+    // The occupied bounds, grown as stones are placed and recomputed only
+    // when a stone on the edge is removed.
+    occupied: Option<(Point, Point)>,
+    rule: WinRule,
+    // The first win in the past record, along with the number of moves
+    // up to and including the winning one.
+    win: Option<(usize, Outcome)>,
+    // Whether the opening stone, the first move in the record, was taken
+    // over by white with `swap_sides`.
+    swapped: bool,
+    // The maximum number of moves that can be undone, if limited.
+    undo_limit: Option<usize>,
+    // The number of moves at the start of the record that can no longer be
+    // undone because of the undo limit.
+    undo_floor: usize,
+    // The threats kept up to date, if enabled.
+    threats: Option<Box<ThreatMap>>,
 }
 
 impl Board {
@@ -236,10 +1132,38 @@ impl Board {
             board: RawBoard::new(),
             bounds,
             record: Vec::new(),
+            times: Vec::new(),
             index: 0,
+            turn: None,
+            resigned: None,
+            occupied: None,
+            rule: WinRule::SixOrMore,
+            win: None,
+            swapped: false,
+            undo_limit: None,
+            undo_floor: 0,
+            threats: None,
         }
     }
 
+    /// Creates an empty board on which at most `limit` moves can be undone.
+    ///
+    /// Once more moves are made, the undo floor rises past the oldest ones:
+    /// moves older than the last `limit` ones can't be undone or jumped back
+    /// to. They still stay in the record, so that the game can be saved,
+    /// and the record keeps growing with every move.
+    pub const fn with_undo_limit(bounds: Bounds, limit: usize) -> Board {
+        let mut board = Board::new(bounds);
+        board.undo_limit = Some(limit);
+        board
+    }
+
+    /// Returns the undo floor, which is the number of moves at the start of
+    /// the record that can no longer be undone because of the undo limit.
+    pub fn undo_floor(&self) -> usize {
+        self.undo_floor
+    }
+
     pub const fn new_infinite() -> Board {
         Board::new(Bounds::Infinite)
     }
@@ -248,6 +1172,15 @@ impl Board {
         Board::new(Bounds::Rect(size, size))
     }
 
+    pub fn from_moves(
+        bounds: Bounds,
+        moves: &[(Point, Stone)],
+    ) -> Result<Board, (usize, SetError)> {
+        let mut board = Board::new(bounds);
+        board.apply_moves(moves)?;
+        Ok(board)
+    }
+
     pub fn bounds(&self) -> Bounds {
         self.bounds
     }
@@ -268,84 +1201,907 @@ impl Board {
         self.board.get(point)
     }
 
+    /// Checks whether there is a stone at `point`.
+    ///
+    /// ```
+    /// # use c6::{Board, Point, Stone};
+    /// let mut board = Board::new_infinite();
+    /// board.set(Point::ORIGIN, Stone::Black).unwrap();
+    /// assert!(board.is_occupied(Point::ORIGIN));
+    /// ```
+    pub fn is_occupied(&self, point: Point) -> bool {
+        self.board.get(point).is_some()
+    }
+
+    /// Checks whether there is no stone at `point`.
+    ///
+    /// Unlike [`Board::can_place`], this does not check the bounds.
+    ///
+    /// ```
+    /// # use c6::{Board, Point};
+    /// let board = Board::new_square(3);
+    /// assert!(board.is_empty_at(Point::new(5, 5)));
+    /// assert!(board.can_place(Point::new(5, 5)).is_err());
+    /// ```
+    pub fn is_empty_at(&self, point: Point) -> bool {
+        self.board.get(point).is_none()
+    }
+
+    /// Gets the slots at many points at once, in the order of the points.
+    /// See [`RawBoard::get_many`].
+    pub fn get_many(&self, points: &[Point]) -> Vec<Option<Stone>> {
+        self.board.get_many(points)
+    }
+
+    pub fn count_by_color(&self) -> (usize, usize) {
+        self.board.count_by_color()
+    }
+
+    /// Counts the stones in a rectangle, as in [`RawBoard::count_in`].
+    pub fn count_stones_in(
+        &self,
+        top_left: Point,
+        size: (u32, u32),
+        stone: Option<Stone>,
+    ) -> usize {
+        self.board.count_in(top_left, size, stone)
+    }
+
+    /// Iterates over the occupancy masks of the board.
+    ///
+    /// See [`RawBoard::bitboards`] for the layout.
+    pub fn bitboards(&self) -> impl Iterator<Item = (u64, u64, u64)> + '_ {
+        self.board.bitboards()
+    }
+
+    /// Returns the index in the record of the move that placed a stone at
+    /// `point`, if there is one on the board.
+    pub fn move_index_of(&self, point: Point) -> Option<usize> {
+        if point == Point::PASS {
+            return None;
+        }
+        self.past_record().iter().position(|&(p, _)| p == point)
+    }
+
+    pub fn played_at(&self, point: Point) -> Option<Stone> {
+        self.get(point)
+    }
+
+    /// Returns the smallest rectangle containing all placed stones as its
+    /// top-left and bottom-right corners, or `None` if the board is empty.
+    ///
+    /// The rectangle is kept up to date as stones are placed, so this takes
+    /// constant time. Removing a stone on its edge makes the board rescan
+    /// its stones to shrink it.
+    pub fn occupied_bounds(&self) -> Option<(Point, Point)> {
+        self.occupied
+    }
+
+    fn scan_occupied_bounds(&self) -> Option<(Point, Point)> {
+        let mut points = self.stones().map(|(point, _)| point);
+        let first = points.next()?;
+        Some(points.fold((first, first), |(min, max), p| {
+            (
+                Point::new(min.x.min(p.x), min.y.min(p.y)),
+                Point::new(max.x.max(p.x), max.y.max(p.y)),
+            )
+        }))
+    }
+
     pub fn past_record(&self) -> &[(Point, Stone)] {
         &self.record[..self.index]
     }
 
+    /// Drops the moves after the current index from the record, so that it
+    /// holds no moves to redo, and a record saved afterwards is minimal.
+    ///
+    /// If `sort_turns` is set, the two stones of each turn are also put in
+    /// canonical order, sorted by [`Point::index`] as with [`sort_moves`].
+    /// The stones of a turn may be placed in either order, so games that
+    /// differ only by this order then have the same record. Turns cut short
+    /// by a pass are left as they are, and times stay with their moves.
+    pub fn compact_record(&mut self, sort_turns: bool) {
+        self.record.truncate(self.index);
+        self.times.truncate(self.index);
+        if !sort_turns {
+            return;
+        }
+
+        let mut start = 0;
+        for end in turn_ends(&self.record).0 {
+            let turn = &self.record[start..end];
+            if let [(a, _), (b, _)] = *turn {
+                if a != Point::PASS && b != Point::PASS && b.index() < a.index() {
+                    self.record.swap(start, start + 1);
+                    self.times.swap(start, start + 1);
+                }
+            }
+            start = end;
+        }
+        self.rescan_win();
+    }
+
+    /// Returns the moves in the past record after the first `index` ones,
+    /// which is empty if `index` is at or past the current index.
+    pub fn moves_since(&self, index: usize) -> &[(Point, Stone)] {
+        &self.past_record()[index.min(self.index)..]
+    }
+
+    /// Returns the past record as plain coordinates, where passes are at
+    /// `(i32::MIN, i32::MIN)` as with [`Point::PASS`].
+    pub fn to_move_list(&self) -> Vec<(i32, i32, Stone)> {
+        self.past_record()
+            .iter()
+            .map(|&(point, stone)| (point.x, point.y, stone))
+            .collect()
+    }
+
+    /// Replays a list of moves as returned by [`Board::to_move_list`] on a
+    /// new board, failing at the first move that can't be placed.
+    pub fn from_move_list(bounds: Bounds, moves: &[(i32, i32, Stone)]) -> Result<Board, SetError> {
+        let mut board = Board::new(bounds);
+        for &(x, y, stone) in moves {
+            let point = Point::new(x, y);
+            if point == Point::PASS {
+                board.push_pass(stone);
+            } else {
+                board.set(point, stone)?;
+            }
+        }
+        Ok(board)
+    }
+
+    /// Iterates over the stones on the board in the order they were placed,
+    /// skipping passes in the record.
+    pub fn stones(&self) -> impl Iterator<Item = (Point, Stone)> + '_ {
+        self.past_record()
+            .iter()
+            .copied()
+            .filter(|&(point, _)| point != Point::PASS)
+    }
+
+    /// Checks whether a stone could be placed at `point` by [`Board::set`],
+    /// without placing it.
+    pub fn can_place(&self, point: Point) -> Result<(), SetError> {
+        if !self.bounds.contains(point) {
+            Err(SetError::OutOfBounds)
+        } else if self.is_occupied(point) {
+            Err(SetError::Occupied)
+        } else {
+            Ok(())
+        }
+    }
+
     pub fn set(&mut self, point: Point, stone: Stone) -> Result<(), SetError> {
         if !self.bounds.contains(point) {
             Err(SetError::OutOfBounds)
         } else if !self.board.set(point, stone) {
             Err(SetError::Occupied)
         } else {
-            self.record.truncate(self.index);
-            self.record.push((point, stone));
-            self.index += 1;
+            self.track_stone(point, stone, true);
+            self.grow_occupied_bounds(point);
+            self.push_move(point, stone);
+            self.check_new_win(point, self.index);
             Ok(())
         }
     }
 
-    pub fn unset(&mut self) -> Option<(Point, Stone)> {
+    /// Same as [`Board::set`], but takes the point by its [`Point::index`],
+    /// for storage working with indexes directly.
+    pub fn set_by_index(&mut self, point_index: u64, stone: Stone) -> Result<(), SetError> {
+        self.set(Point::from_index(point_index), stone)
+    }
+
+    /// Same as [`Board::set`], but also records the time taken by the move.
+    pub fn set_timed(
+        &mut self,
+        point: Point,
+        stone: Stone,
+        elapsed: Duration,
+    ) -> Result<(), SetError> {
+        self.set(point, stone)?;
+        self.times[self.index - 1] = Some(elapsed);
+        Ok(())
+    }
+
+    /// Returns the time taken by the move at `index` in the record, if it
+    /// was recorded.
+    pub fn move_time(&self, index: usize) -> Option<Duration> {
+        self.times.get(index).copied().flatten()
+    }
+
+    /// Passes the rest of the current turn, which is recorded as a move at
+    /// [`Point::PASS`].
+    pub fn pass(&mut self) {
+        self.push_pass(self.turn().stone);
+    }
+
+    /// Checks whether the sides can be swapped by [`Board::swap_sides`],
+    /// which is only the case right after black's opening stone.
+    pub fn can_swap(&self) -> bool {
+        self.index == 1
+            && self.undo_floor == 0
+            && matches!(self.record[0], (point, Stone::Black) if point != Point::PASS)
+    }
+
+    /// Swaps the sides under the pie rule, where instead of replying to
+    /// black's opening stone, white may take it over. The stone turns white,
+    /// and black goes on to play two stones.
+    ///
+    /// Returns `false` and does nothing if the sides can't be swapped.
+    pub fn swap_sides(&mut self) -> bool {
+        if !self.can_swap() {
+            return false;
+        }
+        let (point, _) = self.record[0];
+        self.record.truncate(1);
+        self.times.truncate(1);
+        self.record[0].1 = Stone::White;
+        self.swapped = true;
+        self.board.unset(point);
+        self.track_stone(point, Stone::Black, false);
+        let _ = self.board.set(point, Stone::White);
+        self.track_stone(point, Stone::White, true);
+        self.turn = self.turn.map(|_| Turn {
+            stone: Stone::Black,
+            remaining: 2,
+        });
+        self.resigned = None;
+        true
+    }
+
+    /// Checks whether the sides were swapped by [`Board::swap_sides`], so
+    /// that the opening stone is white.
+    pub fn is_swapped(&self) -> bool {
+        self.swapped && self.index > 0
+    }
+
+    // Marks the opening stone as taken over by white, if it is white's.
+    pub(crate) fn mark_swapped(&mut self) -> bool {
+        self.swapped =
+            matches!(self.record.first(), Some(&(point, Stone::White)) if point != Point::PASS);
+        self.swapped
+    }
+
+    fn push_pass(&mut self, stone: Stone) {
+        self.push_move(Point::PASS, stone);
+    }
+
+    fn push_move(&mut self, point: Point, stone: Stone) {
         if self.index == 0 {
+            self.swapped = false;
+        }
+        self.record.truncate(self.index);
+        self.record.push((point, stone));
+        self.times.truncate(self.index);
+        self.times.push(None);
+        self.index += 1;
+        if let Some(limit) = self.undo_limit {
+            self.undo_floor = self.undo_floor.max(self.index.saturating_sub(limit));
+        }
+        self.turn = self.turn.map(|turn| {
+            if point == Point::PASS {
+                Turn {
+                    stone: stone.opposite(),
+                    remaining: 2,
+                }
+            } else {
+                turn.after(stone)
+            }
+        });
+        self.resigned = None;
+    }
+
+    // Puts a recorded move back on the raw board, where passes leave no trace.
+    fn redo_move(&mut self, (point, stone): (Point, Stone)) {
+        if point != Point::PASS {
+            let _ = self.board.set(point, stone);
+            self.track_stone(point, stone, true);
+            self.grow_occupied_bounds(point);
+        }
+    }
+
+    // Takes a recorded move off the raw board, returning whether the stone
+    // was on the edge of the occupied bounds, which then need a rescan.
+    fn undo_move(&mut self, (point, stone): (Point, Stone)) -> bool {
+        if point == Point::PASS {
+            return false;
+        }
+        self.board.unset(point);
+        self.track_stone(point, stone, false);
+        self.occupied.is_some_and(|(min, max)| {
+            point.x == min.x || point.x == max.x || point.y == min.y || point.y == max.y
+        })
+    }
+
+    // Caches the win completed by the stone at `point` as the move ending at
+    // `index`, unless an earlier win is cached.
+    fn check_new_win(&mut self, point: Point, index: usize) {
+        if self.win.is_none() && point != Point::PASS {
+            if let Some(line) = self.check_win(point) {
+                let stone = self.record[index - 1].1;
+                self.win = Some((index, Outcome::Win { stone, line }));
+            }
+        }
+    }
+
+    pub fn win_rule(&self) -> WinRule {
+        self.rule
+    }
+
+    /// Sets the rule deciding which lines win, replaying the past moves to
+    /// find the first win under the new rule.
+    pub fn set_win_rule(&mut self, rule: WinRule) {
+        self.rule = rule;
+        self.rescan_win();
+    }
+
+    // Finds the cached win again by replaying the past moves.
+    fn rescan_win(&mut self) {
+        self.win = None;
+        for i in (0..self.index).rev() {
+            self.undo_move(self.record[i]);
+        }
+        for i in 0..self.index {
+            self.redo_move(self.record[i]);
+            self.check_new_win(self.record[i].0, i + 1);
+        }
+    }
+
+    // Drops the cached win if its move has been undone.
+    fn forget_undone_win(&mut self) {
+        self.win = self.win.filter(|&(index, _)| index <= self.index);
+    }
+
+    fn grow_occupied_bounds(&mut self, p: Point) {
+        self.occupied = Some(match self.occupied {
+            Some((min, max)) => (
+                Point::new(min.x.min(p.x), min.y.min(p.y)),
+                Point::new(max.x.max(p.x), max.y.max(p.y)),
+            ),
+            None => (p, p),
+        });
+    }
+
+    /// Makes the moves in order, stopping at the first one that fails.
+    ///
+    /// On failure, returns the index of the failed move in `moves` along
+    /// with the error, leaving the moves before it on the board.
+    pub fn apply_moves(&mut self, moves: &[(Point, Stone)]) -> Result<(), (usize, SetError)> {
+        for (i, &(point, stone)) in moves.iter().enumerate() {
+            self.set(point, stone).map_err(|e| (i, e))?;
+        }
+        Ok(())
+    }
+
+    /// Undoes the last move, returning `None` if there is none or it is
+    /// below the undo floor.
+    pub fn unset(&mut self) -> Option<(Point, Stone)> {
+        if self.index == self.undo_floor {
             return None;
         }
         self.index -= 1;
+        self.turn = None;
+        self.resigned = None;
+        self.forget_undone_win();
         let last = self.record[self.index];
 
-        self.board.unset(last.0);
+        if self.undo_move(last) {
+            self.occupied = self.scan_occupied_bounds();
+        }
         Some(last)
     }
 
+    /// Undoes the moves back to the start of the previous turn, returning
+    /// them in the order they were undone.
+    pub fn unset_turn(&mut self) -> Vec<(Point, Stone)> {
+        let mut moves = Vec::new();
+        while let Some(last) = self.unset() {
+            moves.push(last);
+            if self.index == self.undo_floor || self.turn().remaining == 2 {
+                break;
+            }
+        }
+        moves
+    }
+
     pub fn reset(&mut self) -> Option<(Point, Stone)> {
         if self.index >= self.record.len() {
             return None;
         }
         let next = self.record[self.index];
         self.index += 1;
+        self.turn = None;
+        self.resigned = None;
 
-        let _ = self.board.set(next.0, next.1);
+        self.redo_move(next);
+        self.check_new_win(next.0, self.index);
         Some(next)
     }
 
-    pub fn jump(&mut self, index: usize) {
-        assert!(index <= self.record.len());
+    /// Moves through the history to the state after the first `index` moves
+    /// of the record, failing if there aren't that many, or if `index` is
+    /// below the undo floor.
+    pub fn jump(&mut self, index: usize) -> Result<(), JumpError> {
+        if index > self.record.len() || index < self.undo_floor {
+            return Err(JumpError {
+                index,
+                len: self.record.len(),
+                floor: self.undo_floor,
+            });
+        }
         if self.index < index {
             for i in self.index..index {
-                let next = self.record[i];
-                let _ = self.board.set(next.0, next.1);
+                self.redo_move(self.record[i]);
+                self.check_new_win(self.record[i].0, i + 1);
             }
+            self.index = index;
         } else {
+            let mut shrunk = false;
             for i in (index..self.index).rev() {
-                let last = self.record[i];
-                self.board.unset(last.0);
+                shrunk |= self.undo_move(self.record[i]);
             }
+            self.index = index;
+            self.forget_undone_win();
+            if shrunk {
+                self.occupied = self.scan_occupied_bounds();
+            }
+        }
+        self.turn = None;
+        self.resigned = None;
+        Ok(())
+    }
+
+    /// Moves through the history to the state before the first move, or
+    /// at the undo floor, if any.
+    pub fn rewind(&mut self) {
+        self.jump(self.undo_floor)
+            .expect("undo floor within record");
+    }
+
+    /// Moves through the history to the state after the last move.
+    pub fn fast_forward(&mut self) {
+        self.jump(self.record.len()).expect("end of record");
+    }
+
+    /// Returns the number of turns completed in the past record.
+    ///
+    /// A turn is complete once its side has placed its stones (one for the
+    /// opening, two after that) or passed.
+    pub fn turn_count(&self) -> usize {
+        turn_ends(self.past_record()).0.len()
+    }
+
+    /// Iterates over the past record grouped into turns, as counted by
+    /// [`Board::turn_count`], with an incomplete last turn included.
+    pub fn iter_turns(&self) -> impl Iterator<Item = &[(Point, Stone)]> + '_ {
+        let moves = self.past_record();
+        let (ends, rest) = turn_ends(moves);
+        let mut start = 0;
+        ends.into_iter()
+            .chain((rest > 0).then_some(moves.len()))
+            .map(move |end| {
+                let turn = &moves[start..end];
+                start = end;
+                turn
+            })
+    }
+
+    /// Moves through the history to the state after the first `turn` turns
+    /// of the record, i.e. after `1 + 2 * (turn - 1)` moves when nobody
+    /// passes. An incomplete last turn counts, and jumping to it goes to the
+    /// end of the record.
+    ///
+    /// Fails like [`Board::jump`] if the turn starts below the undo floor.
+    pub fn jump_to_turn(&mut self, turn: usize) -> Result<(), JumpToTurnError> {
+        let (ends, rest) = turn_ends(&self.record);
+        let len = ends.len() + usize::from(rest > 0);
+        let index = match turn {
+            0 => 0,
+            _ if turn <= ends.len() => ends[turn - 1],
+            _ if turn == len => self.record.len(),
+            _ => return Err(JumpToTurnError { turn, len }),
+        };
+        if index < self.undo_floor {
+            return Err(JumpToTurnError { turn, len });
+        }
+        self.jump(index).expect("index within history");
+        Ok(())
+    }
+
+    /// Captures the current state of the board, to be restored later by
+    /// [`Board::restore`] after making some moves.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            index: self.index,
+            last: self.past_record().last().copied(),
+            turn: self.turn,
+            resigned: self.resigned,
+            occupied: self.occupied,
+        }
+    }
+
+    /// Undoes the moves made since a snapshot was taken and drops them from
+    /// the record, restoring the state of the board at that time.
+    ///
+    /// Fails and does nothing if moves before the snapshot have been undone
+    /// since, if the snapshot is below the undo floor, or if the move before
+    /// it is not the one on this board, as for a snapshot of another board.
+    pub fn restore(&mut self, snap: &Snapshot) -> Result<(), RestoreError> {
+        if snap.index > self.index {
+            return Err(RestoreError::Ahead);
+        }
+        if snap.index < self.undo_floor {
+            return Err(RestoreError::BelowFloor);
+        }
+        if self.record[..snap.index].last() != snap.last.as_ref() {
+            return Err(RestoreError::Mismatch);
         }
-        self.index = index;
+        for i in (snap.index..self.index).rev() {
+            self.undo_move(self.record[i]);
+        }
+        self.record.truncate(snap.index);
+        self.times.truncate(snap.index);
+        self.index = snap.index;
+        self.swapped &= snap.index > 0;
+        self.forget_undone_win();
+        self.turn = snap.turn;
+        self.resigned = snap.resigned;
+        self.occupied = snap.occupied;
+        Ok(())
+    }
+
+    /// Returns whose turn it is and how many stones they have left to place.
+    ///
+    /// This is the turn set by [`Board::set_turn`] and kept up to date by
+    /// [`Board::set`], or the one inferred from the record if there is none.
+    pub fn turn(&self) -> Turn {
+        self.turn.unwrap_or_else(|| self.inferred_turn())
+    }
+
+    /// Returns the stone to play next, as in [`Board::turn`].
+    pub fn next_to_play(&self) -> Stone {
+        self.turn().stone
+    }
+
+    /// Checks whether the next stone starts a new turn, as in
+    /// [`Board::turn`]. This includes the opening stone, which makes up a
+    /// turn by itself.
+    pub fn is_first_of_turn(&self) -> bool {
+        self.index == 0 || self.turn().remaining == 2
+    }
+
+    /// Sets the turn explicitly, e.g. for an edited position.
+    ///
+    /// Moving through the history with `unset`, `reset` or `jump` discards
+    /// the explicit turn and falls back to inferring it from the record, and
+    /// withdraws a resignation as placing a stone does.
+    ///
+    /// Fails and does nothing unless the turn has 1 or 2 stones remaining.
+    pub fn set_turn(&mut self, turn: Turn) -> Result<(), SetTurnError> {
+        if !(1..=2).contains(&turn.remaining) {
+            return Err(SetTurnError {
+                remaining: turn.remaining,
+            });
+        }
+        self.turn = Some(turn);
+        Ok(())
+    }
+
+    /// Iterates over the points in a rectangle in row-major order, along with
+    /// their occupancy.
+    ///
+    /// Out-of-bounds points are yielded as empty unless `skip_out_of_bounds`
+    /// is set, in which case they are skipped.
+    pub fn region(
+        &self,
+        top_left: Point,
+        size: (u32, u32),
+        skip_out_of_bounds: bool,
+    ) -> impl Iterator<Item = (Point, Option<Stone>)> + '_ {
+        let (width, height) = size;
+        (0..height)
+            .flat_map(move |y| (0..width).map(move |x| top_left + Point::new(x as i32, y as i32)))
+            .filter(move |&point| !skip_out_of_bounds || self.bounds.contains(point))
+            .map(|point| (point, self.get(point)))
+    }
+
+    /// Walks from `point` (inclusive) along `dir` for as long as the points
+    /// are within bounds, yielding their occupancy.
+    ///
+    /// The walk stops at the edges of the coordinate space on an infinite
+    /// board. See [`Board::line_through_rev`] for the other half of the line.
+    pub fn line_through(
+        &self,
+        point: Point,
+        dir: Direction,
+    ) -> impl Iterator<Item = (Point, Option<Stone>)> + '_ {
+        self.ray(point, dir.delta())
+    }
+
+    /// Same as [`Board::line_through`], but walks in the opposite direction.
+    pub fn line_through_rev(
+        &self,
+        point: Point,
+        dir: Direction,
+    ) -> impl Iterator<Item = (Point, Option<Stone>)> + '_ {
+        let (dx, dy) = dir.delta();
+        self.ray(point, (-dx, -dy))
+    }
+
+    fn ray(
+        &self,
+        point: Point,
+        (dx, dy): (i32, i32),
+    ) -> impl Iterator<Item = (Point, Option<Stone>)> + '_ {
+        let mut reader = self.board.reader();
+        iter::successors(Some(point), move |p| {
+            Some(Point::new(p.x.checked_add(dx)?, p.y.checked_add(dy)?))
+        })
+        .take_while(|&p| self.bounds.contains(p))
+        .map(move |p| (p, reader.get(p)))
+    }
+
+    /// Returns the eight adjacent points of [`Point::neighbors8`] where a
+    /// stone could be placed on an empty board, leaving out those beyond
+    /// the bounds.
+    pub fn neighbors_in_bounds(&self, p: Point) -> Vec<Point> {
+        p.neighbors8()
+            .into_iter()
+            .filter(|&q| self.bounds.contains(q))
+            .collect()
     }
 
+    pub fn empty_neighbors_within(&self, center: Point, radius: u32) -> Vec<Point> {
+        center
+            .disk(radius)
+            .filter(|&point| self.bounds.contains(point) && self.is_empty_at(point))
+            .collect()
+    }
+
+    /// Returns the empty points within a Chebyshev distance of 2 of any
+    /// placed stone, sorted by index.
+    pub fn candidate_moves(&self) -> Vec<Point> {
+        if let Some(points) = self.tracked_candidates() {
+            return points;
+        }
+        let mut points: Vec<Point> = self
+            .stones()
+            .flat_map(|(point, _)| self.empty_neighbors_within(point, 2))
+            .collect();
+        sort_points(&mut points);
+        points.dedup();
+        points
+    }
+
+    /// Infers the turn from the past record, returning the stone to play
+    /// and whether it has only one stone to place.
+    #[deprecated(note = "use `Board::next_to_play` and `Board::is_first_of_turn` instead")]
     pub fn infer_turn(&self) -> (Stone, bool) {
+        let turn = self.inferred_turn();
+        (turn.stone, turn.remaining == 1)
+    }
+
+    fn inferred_turn(&self) -> Turn {
+        let turn = |stone, remaining| Turn { stone, remaining };
         if self.index == 0 {
-            return (Stone::Black, true);
+            return turn(Stone::Black, 1);
         }
 
-        let last = self.record[self.index - 1].1;
+        let (last_point, last) = self.record[self.index - 1];
+        if last_point == Point::PASS {
+            return turn(last.opposite(), 2);
+        }
         if self.index == 1 {
-            return (Stone::White, last == Stone::White);
+            // Once the sides are swapped, black is to play two stones.
+            return match (self.swapped, last) {
+                (true, _) => turn(Stone::Black, 2),
+                (false, Stone::Black) => turn(Stone::White, 2),
+                (false, Stone::White) => turn(Stone::White, 1),
+            };
         }
 
         let last_prev = self.record[self.index - 2].1;
         if last == last_prev {
-            (last.opposite(), false)
+            turn(last.opposite(), 2)
         } else {
-            (last, true)
+            turn(last, 1)
         }
     }
 }
 
+/// Returns the number of moves at the end of each complete turn in `moves`,
+/// along with the number of moves in the incomplete turn after them.
+fn turn_ends(moves: &[(Point, Stone)]) -> (Vec<usize>, usize) {
+    let mut ends = Vec::new();
+    let mut len = 0;
+    for (i, &(point, stone)) in moves.iter().enumerate() {
+        // A turn cut short by the other side is complete as well.
+        if len > 0 && stone != moves[i - 1].1 {
+            ends.push(i);
+            len = 0;
+        }
+        len += 1;
+        let limit = if len == i + 1 { 1 } else { 2 };
+        if point == Point::PASS || len == limit {
+            ends.push(i + 1);
+            len = 0;
+        }
+    }
+    (ends, len)
+}
+
+/// Collects moves onto an infinite board, skipping illegal ones.
+///
+/// Use [`Board::from_moves`] to find out about illegal moves instead.
+impl FromIterator<(Point, Stone)> for Board {
+    fn from_iter<I: IntoIterator<Item = (Point, Stone)>>(iter: I) -> Board {
+        let mut board = Board::new_infinite();
+        board.extend(iter);
+        board
+    }
+}
+
+/// Makes the moves in order, skipping illegal ones.
+///
+/// Use [`Board::apply_moves`] to find out about illegal moves instead.
+impl Extend<(Point, Stone)> for Board {
+    fn extend<I: IntoIterator<Item = (Point, Stone)>>(&mut self, iter: I) {
+        for (point, stone) in iter {
+            let _ = self.set(point, stone);
+        }
+    }
+}
+
+// Returns twice the coordinates of the center of a board, which lies
+// between two points along an axis of even size.
+fn doubled_center(bounds: Bounds) -> (i64, i64) {
+    match bounds {
+        Bounds::Infinite | Bounds::Limited(_) => (0, 0),
+        Bounds::Rect(width, height) => {
+            let (x_min, x_max) = zigzag_range(width);
+            let (y_min, y_max) = zigzag_range(height);
+            (x_min as i64 + x_max as i64, y_min as i64 + y_max as i64)
+        }
+    }
+}
+
+impl Board {
+    // Returns the stones on the board sorted by index.
+    fn position(&self) -> Vec<(u64, Stone)> {
+        let mut stones: Vec<_> = self
+            .stones()
+            .map(|(point, stone)| (point.index(), stone))
+            .collect();
+        stones.sort_unstable_by_key(|&(i, _)| i);
+        stones
+    }
+
+    /// Checks whether two boards have the same past record, no matter what
+    /// their bounds are.
+    pub fn moves_equal(&self, other: &Board) -> bool {
+        self.past_record() == other.past_record()
+    }
+
+    /// Checks whether two boards have the same stones on them, no matter
+    /// in which order they were placed or what their bounds are.
+    pub fn position_equal(&self, other: &Board) -> bool {
+        self.position() == other.position()
+    }
+
+    /// Returns a copy of the board with every move in the record mapped by a
+    /// symmetry about the center of the board, in the same order. The width
+    /// and height of a bounded board are swapped along with the axes.
+    ///
+    /// The center of an infinite board is the origin, and points beyond the
+    /// range of `i32` wrap around as in [`Point::transform`].
+    pub fn transform(&self, sym: Symmetry) -> Board {
+        let bounds = match self.bounds {
+            Bounds::Rect(width, height) if sym.swaps_axes() => Bounds::Rect(height, width),
+            bounds => bounds,
+        };
+        let (sx, sy) = doubled_center(self.bounds);
+        let (tx, ty) = doubled_center(bounds);
+        let map = |point: Point| {
+            let (x, y) = sym.apply(point.x as i64 * 2 - sx, point.y as i64 * 2 - sy);
+            Point::new(((x + tx) / 2) as i32, ((y + ty) / 2) as i32)
+        };
+
+        let mut board = Board::new(bounds);
+        board.rule = self.rule;
+        for &(point, stone) in &self.record {
+            if point == Point::PASS {
+                board.push_pass(stone);
+            } else {
+                board
+                    .set(map(point), stone)
+                    .expect("symmetries should map the board onto itself");
+            }
+        }
+        board.jump(self.index).expect("index within record");
+        board.times.clone_from(&self.times);
+        board.turn = self.turn;
+        board.resigned = self.resigned;
+        board.swapped = self.swapped;
+        board.undo_limit = self.undo_limit;
+        board.undo_floor = self.undo_floor;
+        if self.is_tracking_threats() {
+            board.enable_threat_tracking();
+        }
+        board
+    }
+
+    /// Turns every black stone white and every white stone black, on the
+    /// board and in the whole record, as if the sides were swapped.
+    ///
+    /// The turn, a resignation and a win change sides along with them. A
+    /// game with the sides swapped turns into one without, and vice versa
+    /// for a game opened by black.
+    pub fn invert_colors(&mut self) {
+        self.board.invert_colors();
+        for (_, stone) in &mut self.record {
+            *stone = stone.opposite();
+        }
+        if self.swapped {
+            self.swapped = false;
+        } else {
+            self.mark_swapped();
+        }
+        if let Some(turn) = &mut self.turn {
+            turn.stone = turn.stone.opposite();
+        }
+        self.resigned = self.resigned.map(Stone::opposite);
+        if let Some((_, Outcome::Win { stone, .. })) = &mut self.win {
+            *stone = stone.opposite();
+        }
+        if self.is_tracking_threats() {
+            self.enable_threat_tracking();
+        }
+    }
+
+    /// Checks whether the stones on this board can be mapped onto those on
+    /// another by one of the eight symmetries about the centers of the boards.
+    pub fn symmetric_equal(&self, other: &Board) -> bool {
+        let target = other.position();
+        let (sx, sy) = doubled_center(self.bounds);
+        let (tx, ty) = doubled_center(other.bounds);
+
+        Symmetry::ALL.iter().any(|&sym| {
+            let mut stones = Vec::with_capacity(target.len());
+            for (point, stone) in self.stones() {
+                let (x, y) = sym.apply(point.x as i64 * 2 - sx, point.y as i64 * 2 - sy);
+                let (x, y) = (x + tx, y + ty);
+                // Off-center points can't map between odd and even sizes.
+                if x % 2 != 0 || y % 2 != 0 {
+                    return false;
+                }
+                stones.push((Point::new((x / 2) as i32, (y / 2) as i32).index(), stone));
+            }
+            stones.sort_unstable_by_key(|&(i, _)| i);
+            stones == target
+        })
+    }
+}
+
 impl PartialEq for Board {
     fn eq(&self, other: &Board) -> bool {
-        self.bounds == other.bounds && self.record[..self.index] == other.record[..other.index]
+        self.bounds == other.bounds
+            && self.rule == other.rule
+            && self.is_swapped() == other.is_swapped()
+            && self.record[..self.index] == other.record[..other.index]
     }
 }
 
 impl Eq for Board {}
+
+// Hashes what `PartialEq` compares, so boards reaching the same position in
+// different orders hash differently. Hash `Board::stones` sorted by index
+// to deduplicate positions instead.
+impl Hash for Board {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bounds.hash(state);
+        self.rule.hash(state);
+        self.is_swapped().hash(state);
+        self.past_record().hash(state);
+    }
+}
@@ -1,27 +1,68 @@
-use c6::{Board, Point, Stone};
+use c6::{is_star_point, Board, Bounds, ByStone, Point, Stone};
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    cursor::Show,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use std::{
     env,
     error::Error,
+    ffi::OsString,
     fs::File,
-    io::{self, BufReader, BufWriter},
+    io::{self, prelude::*, BufReader, BufWriter},
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::{Duration, Instant},
 };
 use tui::{
     backend::{Backend, CrosstermBackend},
     buffer::Buffer,
     layout::Rect,
-    style::Style,
-    widgets::Widget,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Widget},
     Terminal,
 };
+use unicode_width::UnicodeWidthStr;
 
 fn main() -> Result<(), Box<dyn Error>> {
-    // setup terminal
+    let mut tabs = Vec::new();
+    let mut theme = &Theme::UNICODE;
+    let mut commands_path = None;
+    let mut args = env::args_os().skip(1).peekable();
+    if args.next_if_eq("export").is_some() {
+        return export(args);
+    }
+    while let Some(arg) = args.next() {
+        if arg == "--ascii" {
+            theme = &Theme::ASCII;
+            continue;
+        }
+        if arg == "--theme" {
+            let name = args.next().unwrap_or_default();
+            theme = Theme::by_name(&name.to_string_lossy())
+                .ok_or("unknown theme, expected unicode, ascii or colorblind")?;
+            continue;
+        }
+        if arg == "--commands" {
+            commands_path = Some(PathBuf::from(args.next().ok_or("expected a command file")?));
+            continue;
+        }
+        let path = PathBuf::from(arg);
+        match load_tab(&path) {
+            Ok(tab) => tabs.push(tab),
+            Err(err) => eprintln!("{}: {}", path.display(), err),
+        }
+    }
+    if tabs.is_empty() {
+        tabs.push(Tab::new(None, Board::new_infinite()));
+    }
+
+    // setup terminal, which is restored when the guard is dropped, even if
+    // a later step fails or the app panics
     enable_raw_mode()?;
+    let guard = Guard(restore_terminal);
     let mut stdout = io::stdout();
     execute!(
         stdout,
@@ -31,201 +72,1218 @@ fn main() -> Result<(), Box<dyn Error>> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // create app and run it
-    let res = run_app(&mut terminal);
+    // create app and run it, with commands read from a file if given
+    let (sender, commands) = mpsc::channel();
+    if let Some(path) = commands_path {
+        send_commands(path, sender);
+    }
+    let res = run_app(&mut terminal, &mut tabs, theme, &mut read_key, &commands);
 
-    // restore terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
+    drop(guard);
+    if let Err(err) = res {
+        println!("{:?}", err)
+    }
+
+    Ok(())
+}
+
+// Runs a cleanup when dropped.
+struct Guard<F: FnMut()>(F);
+
+impl<F: FnMut()> Drop for Guard<F> {
+    fn drop(&mut self) {
+        (self.0)();
+    }
+}
+
+// Restores the terminal on a best-effort basis, as there may be no way left
+// to report the errors.
+fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(
+        io::stdout(),
         LeaveAlternateScreen,
         // DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+        Show
+    );
+}
 
-    if let Err(err) = res {
-        println!("{:?}", err)
+const DEFAULT_SAVE_PATH: &str = "save.c6";
+
+// How long to wait for a key at a time, before checking for commands sent
+// over the channel.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// An input to the event loop, either a key typed locally or a command sent
+/// over a channel by something else, such as a remote review session.
+#[derive(Debug)]
+enum Command {
+    Key(KeyEvent),
+    /// Redraws the screen, as after a resize.
+    Redraw,
+    /// Places a stone of the side to play on the current tab.
+    Place(Point),
+    /// Passes the rest of the turn on the current tab.
+    Pass,
+    /// Undoes the last move on the current tab.
+    Undo,
+    /// Quits without checking that the games are saved.
+    Quit,
+}
+
+// Parses a line of a command file, like `place J10`, `place (3, -2)`,
+// `pass`, `undo` or `quit`.
+fn parse_command(line: &str) -> Option<Command> {
+    match line.trim().split_once(' ') {
+        Some(("place", point)) => {
+            let point = point.trim();
+            Point::from_algebraic(point)
+                .or_else(|| point.parse().ok())
+                .map(Command::Place)
+        }
+        Some(_) => None,
+        None => match line.trim() {
+            "pass" => Some(Command::Pass),
+            "undo" => Some(Command::Undo),
+            "quit" => Some(Command::Quit),
+            _ => None,
+        },
+    }
+}
+
+// Reads commands from a file on another thread, such as a named pipe fed by
+// a remote review session, and sends them over the channel until the file
+// ends or the app quits. Lines that aren't commands are skipped.
+fn send_commands(path: PathBuf, sender: Sender<Command>) {
+    thread::spawn(move || -> io::Result<()> {
+        for line in BufReader::new(File::open(path)?).lines() {
+            if let Some(command) = parse_command(&line?) {
+                if sender.send(command).is_err() {
+                    break;
+                }
+            }
+        }
+        Ok(())
+    });
+}
+
+// Waits up to `timeout` for a key from the terminal, returning `None` if
+// none comes in time.
+fn read_key(timeout: Duration) -> io::Result<Option<Command>> {
+    if !event::poll(timeout)? {
+        return Ok(None);
+    }
+    Ok(Some(match event::read()? {
+        Event::Key(event) if event.kind == KeyEventKind::Press => Command::Key(event),
+        _ => Command::Redraw,
+    }))
+}
+
+// Waits for the next command, either from `read_key` or over the channel,
+// returning `None` if none comes before the timeout, if any.
+fn next_command(
+    read_key: &mut impl FnMut(Duration) -> io::Result<Option<Command>>,
+    commands: &Receiver<Command>,
+    timeout: Option<Duration>,
+) -> io::Result<Option<Command>> {
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    loop {
+        // A disconnected channel leaves the keys to drive the loop.
+        if let Ok(command) = commands.try_recv() {
+            return Ok(Some(command));
+        }
+        let wait = match deadline {
+            Some(deadline) => deadline
+                .saturating_duration_since(Instant::now())
+                .min(POLL_INTERVAL),
+            None => POLL_INTERVAL,
+        };
+        if let Some(command) = read_key(wait)? {
+            return Ok(Some(command));
+        }
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return Ok(None);
+        }
+    }
+}
+
+const REPLAY_DELAY_RANGE: (Duration, Duration) =
+    (Duration::from_millis(50), Duration::from_millis(6400));
+
+// The maximum number of columns and rows of points in the minimap.
+const MINIMAP_SIZE: (u16, u16) = (16, 8);
+
+// The characters and colors to draw the board with.
+struct Theme {
+    black: char,
+    white: char,
+    stone_colors: ByStone<Color>,
+    empty: char,
+    star_point: char,
+    threat: char,
+    // Winning spots for black only, white only and both, in that order.
+    threat_colors: [Color; 3],
+    // The marks around the cursor and the stones of the last turn.
+    cursor: [char; 2],
+    last_move: [char; 2],
+    // The number of columns taken by each point, at least 2 so that the
+    // marks fit in the gaps between the points.
+    cell_width: u16,
+    // The color of the cursor after an illegal move.
+    error_color: Color,
+}
+
+impl Theme {
+    const UNICODE: Theme = Theme {
+        black: '●',
+        white: '○',
+        stone_colors: ByStone::new(Color::Reset, Color::Reset),
+        empty: '·',
+        star_point: '+',
+        threat: '×',
+        threat_colors: [Color::Red, Color::Blue, Color::Magenta],
+        cursor: ['(', ')'],
+        last_move: ['`', '`'],
+        cell_width: 2,
+        error_color: Color::Red,
+    };
+
+    // For terminals that misalign the circles.
+    const ASCII: Theme = Theme {
+        black: 'X',
+        white: 'O',
+        empty: '.',
+        star_point: '+',
+        threat: '*',
+        ..Theme::UNICODE
+    };
+
+    // For colorblind users, with the stones told apart by shape as well as
+    // by fill, and colors from the Okabe-Ito palette.
+    const COLORBLIND: Theme = Theme {
+        white: '◇',
+        stone_colors: ByStone::new(Color::Reset, Color::Rgb(86, 180, 233)),
+        // Orange, bluish green and yellow, apart from the sky blue stones.
+        threat_colors: [
+            Color::Rgb(230, 159, 0),
+            Color::Rgb(0, 158, 115),
+            Color::Rgb(240, 228, 66),
+        ],
+        // Vermillion.
+        error_color: Color::Rgb(213, 94, 0),
+        ..Theme::UNICODE
+    };
+
+    fn by_name(name: &str) -> Option<&'static Theme> {
+        match name {
+            "unicode" => Some(&Theme::UNICODE),
+            "ascii" => Some(&Theme::ASCII),
+            "colorblind" => Some(&Theme::COLORBLIND),
+            _ => None,
+        }
+    }
+
+    fn stone(&self, stone: Stone) -> char {
+        match stone {
+            Stone::Black => self.black,
+            Stone::White => self.white,
+        }
+    }
+}
+
+struct Tab {
+    path: Option<PathBuf>,
+    board: Board,
+    term_center: Point,
+    cursor: Point,
+    saved: bool,
+}
+
+impl Tab {
+    fn new(path: Option<PathBuf>, board: Board) -> Tab {
+        Tab {
+            path,
+            board,
+            term_center: Point::ORIGIN,
+            cursor: Point::ORIGIN,
+            saved: true,
+        }
+    }
+
+    fn name(&self) -> String {
+        match &self.path {
+            Some(path) => match path.file_name() {
+                Some(name) => name.to_string_lossy().into_owned(),
+                None => path.display().to_string(),
+            },
+            None => "(new)".into(),
+        }
+    }
+
+    /// Moves the cursor to the point given as `(x, y)` or in algebraic
+    /// notation and centers the view on it, returning a status message.
+    fn go_to(&mut self, input: &str) -> String {
+        let Some(point) = input.parse().ok().or_else(|| Point::from_algebraic(input)) else {
+            return format!("Invalid coordinate: {input}");
+        };
+        if !self.board.bounds().contains(point) {
+            return format!("Out of bounds: {point}");
+        }
+        self.cursor = point;
+        self.term_center = point;
+        String::new()
+    }
+
+    fn save(&mut self) {
+        let path = self
+            .path
+            .get_or_insert_with(|| PathBuf::from(DEFAULT_SAVE_PATH));
+        if let Ok(file) = File::create(path) {
+            self.saved = self.board.save_record(BufWriter::new(file)).is_ok();
+        }
+    }
+}
+
+fn load_tab(path: &Path) -> Result<Tab, Box<dyn Error>> {
+    let board = Board::load_record(BufReader::new(File::open(path)?))?;
+    Ok(Tab::new(Some(path.into()), board))
+}
+
+const EXPORT_USAGE: &str = "usage: c6 export <file> [--format json|notation|ascii]";
+// The largest number of points per side drawn by `c6 export --format ascii`.
+const ASCII_MAX_SIZE: u32 = 1024;
+
+// Runs `c6 export`, which writes a record in another format to stdout
+// without starting the app.
+fn export(mut args: impl Iterator<Item = OsString>) -> Result<(), Box<dyn Error>> {
+    let mut path = None;
+    let mut format = OsString::from("notation");
+    while let Some(arg) = args.next() {
+        if arg == "--format" {
+            format = args.next().ok_or(EXPORT_USAGE)?;
+        } else if path.is_none() {
+            path = Some(PathBuf::from(arg));
+        } else {
+            return Err(EXPORT_USAGE.into());
+        }
     }
+    let path = path.ok_or(EXPORT_USAGE)?;
+    let board = Board::load_record(BufReader::new(File::open(path)?))?;
 
+    let output = match format.to_str() {
+        #[cfg(feature = "json")]
+        Some("json") => board.to_json(),
+        #[cfg(not(feature = "json"))]
+        Some("json") => return Err("json support is not enabled in this build".into()),
+        Some("notation") => board.to_notation(),
+        Some("ascii") => to_ascii(&board)?,
+        _ => return Err(EXPORT_USAGE.into()),
+    };
+    writeln!(io::stdout(), "{output}")?;
     Ok(())
 }
 
-fn run_app<B: Backend>(terminal: &mut Terminal<B>) -> Result<(), Box<dyn Error>> {
-    let mut board = match env::args().nth(1) {
-        Some(path) => Board::load_record(BufReader::new(File::open(path)?))?,
-        None => Board::new_infinite(),
+/// Draws the board with the characters of the ASCII theme, one row of
+/// points per line. Unbounded boards are drawn within their occupied
+/// bounds, so an empty one is drawn as nothing.
+///
+/// Fails if the drawing would be more than `ASCII_MAX_SIZE` points wide or
+/// high.
+fn to_ascii(board: &Board) -> Result<String, Box<dyn Error>> {
+    let bounds = board.bounds();
+    let (top_left, bottom_right) = match bounds {
+        Bounds::Rect(..) => (
+            bounds.clamp_point(Point::new(i32::MIN, i32::MIN)),
+            bounds.clamp_point(Point::new(i32::MAX, i32::MAX)),
+        ),
+        _ => match board.occupied_bounds() {
+            Some(occupied) => occupied,
+            None => return Ok(String::new()),
+        },
     };
-    let mut term_center = Point::ORIGIN;
-    let mut cursor = Point::ORIGIN;
-    let (mut stone, mut swap) = board.infer_turn();
-    let mut saved = true;
+    let width = bottom_right.x.abs_diff(top_left.x).saturating_add(1);
+    let height = bottom_right.y.abs_diff(top_left.y).saturating_add(1);
+    if width > ASCII_MAX_SIZE || height > ASCII_MAX_SIZE {
+        return Err(format!(
+            "board is too large to draw, at most {ASCII_MAX_SIZE} points per side"
+        )
+        .into());
+    }
+
+    let theme = &Theme::ASCII;
+    let mut rows = vec![String::new(); height as usize];
+    for (point, slot) in board.region(top_left, (width, height), false) {
+        let ch = match slot {
+            Some(stone) => theme.stone(stone),
+            None if !bounds.contains(point) => ' ',
+            None if is_star_point(bounds, point) => theme.star_point,
+            None => theme.empty,
+        };
+        let row = &mut rows[point.y.abs_diff(top_left.y) as usize];
+        if !row.is_empty() {
+            row.push(' ');
+        }
+        row.push(ch);
+    }
+    Ok(rows.join("\n"))
+}
+
+fn run_app<B: Backend>(
+    terminal: &mut Terminal<B>,
+    tabs: &mut [Tab],
+    theme: &Theme,
+    read_key: &mut impl FnMut(Duration) -> io::Result<Option<Command>>,
+    commands: &Receiver<Command>,
+) -> Result<(), Box<dyn Error>> {
+    let mut current = 0;
+    let mut show_threats = false;
+    let mut show_preview = false;
+    let mut show_minimap = false;
+    // Whether the view follows the cursor, instead of panning only when the
+    // cursor reaches an edge.
+    let mut follow = false;
+    // The input typed so far while the goto prompt is open.
+    let mut prompt: Option<String> = None;
+    let mut status = String::new();
+    // Whether the cursor is flashed for an illegal move, until the next key.
+    let mut flash = false;
+    let mut replaying = false;
+    let mut replay_delay = Duration::from_millis(400);
+    // The size of the board view as last rendered, for paging the cursor.
+    let mut view = (0, 0);
 
     loop {
-        let cursor_msg = format!("Cursor: ({}, {})", cursor.x, cursor.y);
+        let tab_count = tabs.len();
+        let all_saved = tabs.iter().all(|tab| tab.saved);
+        let tab = &mut tabs[current];
+        let turn = tab.board.turn();
+        if follow {
+            tab.term_center = tab.cursor;
+        }
+
+        let file_msg = format!("File: {} ({}/{})", tab.name(), current + 1, tab_count);
+        let cursor_msg = format!("Cursor: {}", tab.cursor);
         let turn_msg = format!(
-            "{}: {} to play",
-            match stone {
-                Stone::Black => "Black (●)",
-                Stone::White => "White (○)",
-            },
-            if swap { 1 } else { 2 }
+            "{} ({}): {} to play",
+            turn.stone,
+            theme.stone(turn.stone),
+            turn.remaining
         );
+        let status_msg = match &prompt {
+            Some(input) => format!("Go to: {input}_"),
+            None if replaying => format!("Replay: {} ms per move", replay_delay.as_millis()),
+            None => status.clone(),
+        };
+        let threats = if show_threats {
+            [Stone::Black, Stone::White].map(|stone| tab.board.winning_spots(stone))
+        } else {
+            Default::default()
+        };
+        let messages = [&turn_msg[..], &cursor_msg, &file_msg, &status_msg];
         terminal.draw(|f| {
+            view = view_size(f.size(), theme, messages.len());
             f.render_widget(
                 BoardView {
-                    board: &board,
-                    term_center: &mut term_center,
-                    cursor,
-                    messages: [&turn_msg, &cursor_msg],
+                    theme,
+                    board: &tab.board,
+                    term_center: &mut tab.term_center,
+                    cursor: tab.cursor,
+                    flash,
+                    preview: show_preview.then_some(turn.stone),
+                    minimap: show_minimap,
+                    threats: [&threats[0], &threats[1]],
+                    messages: &messages,
                 },
                 f.size(),
             );
         })?;
 
-        let Event::Key(event) = event::read()? else {
-            continue;
+        // While replaying, the next move is made when no command comes in
+        // time.
+        let event = match next_command(read_key, commands, replaying.then_some(replay_delay))? {
+            Some(Command::Key(event)) => event,
+            Some(Command::Redraw) => continue,
+            Some(Command::Place(point)) => {
+                match tab.board.set(point, turn.stone) {
+                    Ok(()) => tab.saved = false,
+                    Err(err) => status = format!("Illegal move: {point} is {err}"),
+                }
+                continue;
+            }
+            Some(Command::Pass) => {
+                tab.board.pass();
+                tab.saved = false;
+                continue;
+            }
+            Some(Command::Undo) => {
+                tab.board.unset();
+                tab.saved = false;
+                continue;
+            }
+            Some(Command::Quit) => return Ok(()),
+            None => {
+                match tab.board.reset() {
+                    Some(_) => tab.saved = false,
+                    None => replaying = false,
+                }
+                continue;
+            }
         };
-        if event.kind != KeyEventKind::Press {
+
+        if let Some(input) = &mut prompt {
+            match event.code {
+                KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(());
+                }
+                KeyCode::Char(c) => input.push(c),
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Esc => prompt = None,
+                KeyCode::Enter => {
+                    status = tab.go_to(input.trim());
+                    prompt = None;
+                }
+                _ => (),
+            }
             continue;
         }
 
-        let prev_cursor = cursor;
+        let (min_delay, max_delay) = REPLAY_DELAY_RANGE;
+        match event.code {
+            KeyCode::Char('+') => {
+                replay_delay = (replay_delay / 2).max(min_delay);
+                continue;
+            }
+            KeyCode::Char('-') => {
+                replay_delay = (replay_delay * 2).min(max_delay);
+                continue;
+            }
+            _ if replaying => {
+                replaying = false;
+                continue;
+            }
+            _ => (),
+        }
 
+        status.clear();
+        flash = false;
+
+        let ctrl = event.modifiers.contains(KeyModifiers::CONTROL);
+        let page_x = (view.0 as i32 / 2).max(1);
+        let page_y = (view.1 as i32 / 2).max(1);
         match event.code {
             KeyCode::Char('q') => {
-                if saved {
+                if all_saved {
                     return Ok(());
                 }
+                status = "Unsaved changes: press S to save or Ctrl+C to quit".into();
             }
-            KeyCode::Char('s') => {
-                if let Ok(file) = File::create("save.c6") {
-                    saved = board.save_record(BufWriter::new(file)).is_ok();
-                }
-            }
+            KeyCode::Char('s') => tab.save(),
+            KeyCode::Tab => current = (current + 1) % tab_count,
+            KeyCode::BackTab => current = (current + tab_count - 1) % tab_count,
             KeyCode::Char('c') => {
                 if event.modifiers.contains(KeyModifiers::CONTROL) {
                     return Ok(());
                 }
-                term_center = Point::ORIGIN;
-                cursor = Point::ORIGIN;
+                tab.term_center = Point::ORIGIN;
+                tab.cursor = Point::ORIGIN;
             }
+            KeyCode::Char('t') => show_threats = !show_threats,
+            KeyCode::Char('g') => show_preview = !show_preview,
+            KeyCode::Char('m') => show_minimap = !show_minimap,
+            KeyCode::Char('z') => tab.term_center = tab.cursor,
+            KeyCode::Char('f') => follow = !follow,
+            KeyCode::Char('/') => prompt = Some(String::new()),
+            KeyCode::Char('r') => replaying = true,
             KeyCode::Char('p') => {
-                stone = stone.opposite();
-                swap = false;
-            }
-            KeyCode::Char(' ') | KeyCode::Enter => {
-                if board.set(cursor, stone).is_ok() {
-                    if swap {
-                        stone = stone.opposite();
-                    }
-                    swap = !swap;
-                    saved = false;
-                }
+                tab.board.pass();
+                tab.saved = false;
             }
+            KeyCode::Char('w') => {
+                // Nothing changes if the sides can't be swapped.
+                tab.saved &= !tab.board.swap_sides();
+            }
+            KeyCode::Char(' ') | KeyCode::Enter => match tab.board.set(tab.cursor, turn.stone) {
+                Ok(()) => tab.saved = false,
+                Err(err) => {
+                    status = format!("Illegal move: {} is {err}", tab.cursor);
+                    flash = true;
+                }
+            },
             KeyCode::Char('[') => {
-                board.unset();
-                (stone, swap) = board.infer_turn();
-                saved = false;
+                tab.board.unset();
+                tab.saved = false;
+            }
+            KeyCode::Backspace => {
+                tab.board.unset_turn();
+                tab.saved = false;
             }
             KeyCode::Char(']') => {
-                board.reset();
-                (stone, swap) = board.infer_turn();
-                saved = false;
+                tab.board.reset();
+                tab.saved = false;
             }
             KeyCode::Home => {
-                board.jump(0);
-                (stone, swap) = board.infer_turn();
-                saved = false;
+                tab.board.rewind();
+                tab.saved = false;
             }
             KeyCode::End => {
-                board.jump(board.total_count());
-                (stone, swap) = board.infer_turn();
-                saved = false;
-            }
-            KeyCode::Up => cursor.y -= 1,
-            KeyCode::Left => cursor.x -= 1,
-            KeyCode::Down => cursor.y += 1,
-            KeyCode::Right => cursor.x += 1,
+                tab.board.fast_forward();
+                tab.saved = false;
+            }
+            KeyCode::PageUp => tab.cursor.y = tab.cursor.y.saturating_sub(page_y),
+            KeyCode::PageDown => tab.cursor.y = tab.cursor.y.saturating_add(page_y),
+            KeyCode::Up if ctrl => tab.cursor.y = tab.cursor.y.saturating_sub(page_y),
+            KeyCode::Left if ctrl => tab.cursor.x = tab.cursor.x.saturating_sub(page_x),
+            KeyCode::Down if ctrl => tab.cursor.y = tab.cursor.y.saturating_add(page_y),
+            KeyCode::Right if ctrl => tab.cursor.x = tab.cursor.x.saturating_add(page_x),
+            KeyCode::Up => tab.cursor.y = tab.cursor.y.saturating_sub(1),
+            KeyCode::Left => tab.cursor.x = tab.cursor.x.saturating_sub(1),
+            KeyCode::Down => tab.cursor.y = tab.cursor.y.saturating_add(1),
+            KeyCode::Right => tab.cursor.x = tab.cursor.x.saturating_add(1),
             _ => (),
         }
 
-        if !board.bounds().contains(cursor) {
-            cursor = prev_cursor;
-        }
+        tab.cursor = tab.board.bounds().clamp_point(tab.cursor);
     }
 }
 
 struct BoardView<'a> {
+    theme: &'a Theme,
     board: &'a Board,
     term_center: &'a mut Point,
     cursor: Point,
-    messages: [&'a str; 2],
+    // Whether to draw the cursor in the error color.
+    flash: bool,
+    // The stone to preview under the cursor, if any.
+    preview: Option<Stone>,
+    // Whether to draw a minimap of the stones in the top right corner.
+    minimap: bool,
+    // Winning spots for black and white, in that order.
+    threats: [&'a [Point]; 2],
+    messages: &'a [&'a str],
+}
+
+/// Returns the column of the first colon in a message, or 0 if there is
+/// none, counting wide characters as two columns.
+fn colon_column(message: &str) -> u16 {
+    message
+        .split_once(':')
+        .map_or(0, |(head, _)| head.width() as u16)
+}
+
+/// Returns the width and height in points of the board view drawn in
+/// `area` with `theme`, above the given number of message lines.
+///
+/// The first column is left for the mark to the left of the first point.
+fn view_size(area: Rect, theme: &Theme, message_count: usize) -> (u16, u16) {
+    let view_width = area.width.saturating_sub(1) / theme.cell_width;
+    let view_height = area.height.saturating_sub(message_count as u16);
+    (view_width, view_height)
+}
+
+/// Returns the first and last coordinates along an axis of a view of `len`
+/// points around `center`, moving the center as little as possible for the
+/// view to include `cursor` and to stay within the coordinate space.
+fn view_range(center: &mut i32, cursor: i32, len: u16) -> (i32, i32) {
+    let (cursor, len) = (cursor as i64, len as i64);
+    let min = (*center as i64 - len / 2)
+        .max(cursor - len + 1)
+        .min(cursor)
+        .max(i32::MIN as i64)
+        .min(i32::MAX as i64 - len + 1);
+    *center = (min + len / 2) as i32;
+    (min as i32, (min + len - 1) as i32)
 }
 
 impl<'a> Widget for BoardView<'a> {
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        let bounds = self.board.bounds();
-        let view_width = area.width / 2 + area.width % 2 - 1;
-        let view_height = area.height - 2;
-
-        let mut x_min = self.term_center.x - (view_width / 2) as i32;
-        let mut x_max = x_min + view_width as i32 - 1;
-        let mut y_min = self.term_center.y - (view_height / 2) as i32;
-        let mut y_max = y_min + view_height as i32 - 1;
-
-        let dx = self.cursor.x - self.cursor.x.clamp(x_min, x_max);
-        self.term_center.x += dx;
-        x_min += dx;
-        x_max += dx;
-
-        let dy = self.cursor.y - self.cursor.y.clamp(y_min, y_max);
-        self.term_center.y += dy;
-        y_min += dy;
-        y_max += dy;
-
-        for y in 0..view_height {
-            for x in 0..view_width {
-                let point = Point::new(x_min + x as i32, y_min + y as i32);
-                let slot = self.board.get(point);
-                let ch = if bounds.contains(point) {
-                    match slot {
-                        Some(Stone::Black) => '●',
-                        Some(Stone::White) => '○',
-                        None => '·',
-                    }
-                } else {
-                    ' '
-                };
-                buf.get_mut(area.x + x * 2 + 1, area.y + y).set_char(ch);
+    fn render(mut self, area: Rect, buf: &mut Buffer) {
+        let (view_width, view_height) = view_size(area, self.theme, self.messages.len());
+
+        if view_width > 0 && view_height > 0 {
+            let viewport = self.render_board(area, view_width, view_height, buf);
+            if self.minimap {
+                self.render_minimap(area, view_width, view_height, viewport, buf);
+            }
+        }
+
+        for (i, message) in self.messages.iter().enumerate() {
+            let message_y = view_height + i as u16;
+            if message_y >= area.height {
+                break;
             }
+            let center_x = view_width / 2 * self.theme.cell_width + 1;
+            let message_x = center_x.saturating_sub(colon_column(message));
+            if message_x >= area.width {
+                continue;
+            }
+            buf.set_stringn(
+                area.x + message_x,
+                area.y + message_y,
+                message,
+                (area.width - message_x) as usize,
+                Style::default(),
+            );
         }
+    }
+}
 
-        let mut insert_cursor = |pos: Point, left_ch, right_ch| {
+impl<'a> BoardView<'a> {
+    /// Draws the part of the board around the cursor, and returns the
+    /// top-left and bottom-right corners of that part.
+    fn render_board(
+        &mut self,
+        area: Rect,
+        view_width: u16,
+        view_height: u16,
+        buf: &mut Buffer,
+    ) -> (Point, Point) {
+        let bounds = self.board.bounds();
+
+        let (x_min, x_max) = view_range(&mut self.term_center.x, self.cursor.x, view_width);
+        let (y_min, y_max) = view_range(&mut self.term_center.y, self.cursor.y, view_height);
+
+        let top_left = Point::new(x_min, y_min);
+        let region = self
+            .board
+            .region(top_left, (view_width as u32, view_height as u32), false);
+        let theme = self.theme;
+        for (point, slot) in region {
+            let ch = if bounds.contains(point) {
+                match slot {
+                    Some(stone) => theme.stone(stone),
+                    None if is_star_point(bounds, point) => theme.star_point,
+                    None => theme.empty,
+                }
+            } else {
+                ' '
+            };
+            let fg = slot.map_or(Color::Reset, |stone| theme.stone_colors[stone]);
+            let offset = point - top_left;
+            buf.get_mut(
+                area.x + offset.x as u16 * theme.cell_width + 1,
+                area.y + offset.y as u16,
+            )
+            .set_char(ch)
+            .set_fg(fg);
+        }
+
+        let cell = |pos: Point| {
             if (x_min..=x_max).contains(&pos.x) && (y_min..=y_max).contains(&pos.y) {
-                let cur_x = area.x + (pos.x - x_min) as u16 * 2;
-                let cur_y = area.y + (pos.y - y_min) as u16;
-                buf.get_mut(cur_x, cur_y).set_char(left_ch);
-                buf.get_mut(cur_x + 2, cur_y).set_char(right_ch);
+                let cell_x = area.x + (pos.x - x_min) as u16 * theme.cell_width + 1;
+                let cell_y = area.y + (pos.y - y_min) as u16;
+                Some((cell_x, cell_y))
+            } else {
+                None
+            }
+        };
+
+        let [black_threats, white_threats] = self.threats;
+        for &point in black_threats.iter().chain(white_threats) {
+            let [black_color, white_color, both_color] = theme.threat_colors;
+            let color = match (
+                black_threats.contains(&point),
+                white_threats.contains(&point),
+            ) {
+                (true, true) => both_color,
+                (true, false) => black_color,
+                _ => white_color,
+            };
+            if let Some((x, y)) = cell(point) {
+                buf.get_mut(x, y).set_char(theme.threat).set_fg(color);
+            }
+        }
+
+        if let Some(stone) = self.preview {
+            if let (Some((x, y)), None) = (cell(self.cursor), self.board.get(self.cursor)) {
+                buf.get_mut(x, y).set_char(theme.stone(stone)).set_style(
+                    Style::default()
+                        .fg(theme.stone_colors[stone])
+                        .add_modifier(Modifier::DIM),
+                );
+            }
+        }
+
+        let mut insert_cursor = |pos: Point, [left_ch, right_ch]: [char; 2]| {
+            if let Some((x, y)) = cell(pos) {
+                buf.get_mut(x - 1, y).set_char(left_ch);
+                buf.get_mut(x + 1, y).set_char(right_ch);
             }
         };
 
         let record = self.board.past_record();
         if let Some(&(_, last_stone)) = record.last() {
             for &(point, stone) in record.iter().rev() {
-                if stone == last_stone {
-                    insert_cursor(point, '`', '`');
+                if stone == last_stone && point != Point::PASS {
+                    insert_cursor(point, theme.last_move);
                 } else {
                     break;
                 }
             }
         }
 
-        insert_cursor(self.cursor, '(', ')');
+        insert_cursor(self.cursor, theme.cursor);
+        if self.flash {
+            if let Some((x, y)) = cell(self.cursor) {
+                buf.get_mut(x - 1, y).set_fg(theme.error_color);
+                buf.get_mut(x + 1, y).set_fg(theme.error_color);
+            }
+        }
+        (top_left, Point::new(x_max, y_max))
+    }
 
-        for (i, message) in self.messages.iter().enumerate() {
-            let colon_pos = message.chars().position(|b| b == ':').unwrap();
-            let message_x = view_width / 2 * 2 - colon_pos as u16 + 1;
-            buf.set_string(message_x, view_height + i as u16, message, Style::default());
+    /// Draws the occupied bounds of the board and the viewport scaled down
+    /// to fit in a box of at most `MINIMAP_SIZE` cells, over the top right
+    /// corner of the board view. Nothing is drawn for an empty board, or if
+    /// the box does not fit.
+    ///
+    /// Each cell shows the color with more stones in the rectangle it
+    /// covers, which is twice as tall as it is wide in points, so that the
+    /// minimap has the proportions of the board view. The cells overlapping
+    /// the viewport are drawn reversed.
+    fn render_minimap(
+        &self,
+        area: Rect,
+        view_width: u16,
+        view_height: u16,
+        (view_min, view_max): (Point, Point),
+        buf: &mut Buffer,
+    ) {
+        let Some((occupied_min, occupied_max)) = self.board.occupied_bounds() else {
+            return;
+        };
+        let x_min = occupied_min.x.min(view_min.x) as i64;
+        let y_min = occupied_min.y.min(view_min.y) as i64;
+        let width = (occupied_max.x.max(view_max.x) as i64 - x_min + 1) as u64;
+        let height = (occupied_max.y.max(view_max.y) as i64 - y_min + 1) as u64;
+
+        let (max_cols, max_rows) = MINIMAP_SIZE;
+        let scale = (width.div_ceil(max_cols as u64))
+            .max(height.div_ceil(max_rows as u64 * 2))
+            .max(1);
+        let cols = width.div_ceil(scale) as u16;
+        let rows = height.div_ceil(scale * 2) as u16;
+        let scale = scale as i64;
+        // The last point is followed by the column of its right mark.
+        let board_width = (view_width - 1) * self.theme.cell_width + 3;
+        if cols + 2 > board_width || rows + 2 > view_height {
+            return;
+        }
+
+        let left = area.x + board_width - (cols + 2);
+        Block::default()
+            .borders(Borders::ALL)
+            .render(Rect::new(left, area.y, cols + 2, rows + 2), buf);
+
+        let theme = self.theme;
+        for row in 0..rows {
+            for col in 0..cols {
+                let x = x_min + col as i64 * scale;
+                let y = y_min + row as i64 * scale * 2;
+                let top_left = Point::new(x as i32, y as i32);
+                let size = (scale as u32, scale as u32 * 2);
+                let black = self
+                    .board
+                    .count_stones_in(top_left, size, Some(Stone::Black));
+                let white = self
+                    .board
+                    .count_stones_in(top_left, size, Some(Stone::White));
+                let (ch, fg) = match (black, white) {
+                    (0, 0) => (theme.empty, Color::Reset),
+                    _ if black >= white => (theme.black, theme.stone_colors[Stone::Black]),
+                    _ => (theme.white, theme.stone_colors[Stone::White]),
+                };
+
+                let in_view = x <= view_max.x as i64
+                    && x + scale > view_min.x as i64
+                    && y <= view_max.y as i64
+                    && y + scale * 2 > view_min.y as i64;
+                let mut style = Style::default().fg(fg);
+                if in_view {
+                    style = style.add_modifier(Modifier::REVERSED);
+                }
+                buf.get_mut(left + 1 + col, area.y + 1 + row)
+                    .set_char(ch)
+                    .set_style(style);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{cell::Cell, fs};
+    use tui::backend::TestBackend;
+
+    #[test]
+    fn test_guard() {
+        fn setup(cleaned: &Cell<bool>, fail: bool) -> io::Result<()> {
+            let _guard = Guard(|| cleaned.set(true));
+            if fail {
+                return Err(io::ErrorKind::Other.into());
+            }
+            assert!(!cleaned.get());
+            Ok(())
+        }
+
+        for fail in [false, true] {
+            let cleaned = Cell::new(false);
+            assert_eq!(setup(&cleaned, fail).is_err(), fail);
+            assert!(cleaned.get());
+        }
+    }
+
+    #[test]
+    fn test_colon_column() {
+        assert_eq!(colon_column("Cursor: (0, 0)"), 6);
+        assert_eq!(colon_column("Black (●): 1 to play"), 9);
+        assert_eq!(colon_column("黑 (●): 1 to play"), 6);
+        assert_eq!(colon_column("No colon"), 0);
+    }
+
+    #[test]
+    fn test_render_tiny_area() {
+        let board = Board::new_infinite();
+        let messages = ["Black (●): 1 to play", "Cursor: (-100000, 100000)"];
+        for width in 0..8 {
+            for height in 0..5 {
+                let area = Rect::new(3, 2, width, height);
+                let mut buf = Buffer::empty(area);
+                let mut term_center = Point::ORIGIN;
+                BoardView {
+                    theme: &Theme::UNICODE,
+                    board: &board,
+                    term_center: &mut term_center,
+                    cursor: Point::new(-100000, 100000),
+                    flash: false,
+                    preview: Some(Stone::Black),
+                    minimap: false,
+                    threats: [&[], &[]],
+                    messages: &messages,
+                }
+                .render(area, &mut buf);
+            }
+        }
+    }
+
+    #[test]
+    fn test_render_threats() {
+        let board = Board::new_infinite();
+        let area = Rect::new(0, 0, 11, 7);
+        let mut buf = Buffer::empty(area);
+        let mut term_center = Point::ORIGIN;
+        let both = Point::new(1, 1);
+        BoardView {
+            theme: &Theme::UNICODE,
+            board: &board,
+            term_center: &mut term_center,
+            cursor: Point::ORIGIN,
+            flash: false,
+            preview: None,
+            minimap: false,
+            threats: [&[Point::new(-1, 0), both], &[both]],
+            messages: &["Cursor: (0, 0)"],
+        }
+        .render(area, &mut buf);
+
+        // The view is 5 by 6 cells, centered at the origin.
+        let black = buf.get(3, 3);
+        assert_eq!((black.symbol.as_str(), black.fg), ("×", Color::Red));
+        let both = buf.get(7, 4);
+        assert_eq!((both.symbol.as_str(), both.fg), ("×", Color::Magenta));
+    }
+
+    #[test]
+    fn test_render_preview() -> Result<(), Box<dyn Error>> {
+        let mut board = Board::new_infinite();
+        board.set(Point::new(1, 0), Stone::Black)?;
+        let area = Rect::new(0, 0, 11, 7);
+
+        for (cursor, expected) in [(Point::ORIGIN, "○"), (Point::new(1, 0), "●")] {
+            let mut buf = Buffer::empty(area);
+            let mut term_center = Point::ORIGIN;
+            BoardView {
+                theme: &Theme::UNICODE,
+                board: &board,
+                term_center: &mut term_center,
+                cursor,
+                flash: false,
+                preview: Some(Stone::White),
+                minimap: false,
+                threats: [&[], &[]],
+                messages: &["Cursor: (0, 0)"],
+            }
+            .render(area, &mut buf);
+
+            // The preview is only drawn on empty points.
+            let (x, y) = (5 + (cursor.x * 2) as u16, 3);
+            let cell = buf.get(x, y);
+            assert_eq!(cell.symbol, expected);
+            assert_eq!(
+                cell.modifier.contains(Modifier::DIM),
+                cursor == Point::ORIGIN
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_flash() {
+        let board = Board::new_infinite();
+        let area = Rect::new(0, 0, 11, 7);
+        for flash in [false, true] {
+            let mut buf = Buffer::empty(area);
+            let mut term_center = Point::ORIGIN;
+            BoardView {
+                theme: &Theme::UNICODE,
+                board: &board,
+                term_center: &mut term_center,
+                cursor: Point::ORIGIN,
+                flash,
+                preview: None,
+                minimap: false,
+                threats: [&[], &[]],
+                messages: &["Cursor: (0, 0)"],
+            }
+            .render(area, &mut buf);
+
+            let expected = if flash { Color::Red } else { Color::Reset };
+            for x in [4, 6] {
+                assert_eq!(buf.get(x, 3).fg, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_render_far_corner() -> Result<(), Box<dyn Error>> {
+        let mut board = Board::new_infinite();
+        board.set(Point::ORIGIN, Stone::Black)?;
+        let mut tab = Tab::new(None, board);
+        assert_eq!(tab.go_to("(2147483647, -2147483648)"), "");
+
+        let area = Rect::new(0, 0, 11, 5);
+        let mut buf = Buffer::empty(area);
+        BoardView {
+            theme: &Theme::UNICODE,
+            board: &tab.board,
+            term_center: &mut tab.term_center,
+            cursor: tab.cursor,
+            flash: false,
+            preview: None,
+            minimap: true,
+            threats: [&[], &[]],
+            messages: &[],
+        }
+        .render(area, &mut buf);
+
+        // The view stops at the edges of the coordinate space, so the cursor
+        // is drawn in the top-right cell.
+        assert_eq!(tab.term_center, Point::new(i32::MAX - 2, i32::MIN + 2));
+        assert_eq!(buf.get(8, 0).symbol, "(");
+        assert_eq!(buf.get(10, 0).symbol, ")");
+
+        let mut center = 0;
+        assert_eq!(view_range(&mut center, 0, 5), (-2, 2));
+        assert_eq!(view_range(&mut center, 4, 5), (0, 4));
+        assert_eq!(center, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_run_app_commands() -> Result<(), Box<dyn Error>> {
+        let mut terminal = Terminal::new(TestBackend::new(40, 12))?;
+        let mut tabs = vec![Tab::new(None, Board::new_infinite())];
+        let (sender, commands) = mpsc::channel();
+        let key = |c| Command::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        for command in [
+            Command::Place(Point::ORIGIN),
+            Command::Place(Point::new(1, 0)),
+            Command::Undo,
+            Command::Place(Point::new(0, 1)),
+            Command::Pass,
+            key(']'),
+        ] {
+            sender.send(command)?;
+        }
+        // Keys are read once no commands are waiting, after which an illegal
+        // move is sent before quitting.
+        let mut typed = false;
+        let mut read_key = |_| {
+            if typed {
+                sender.send(Command::Place(Point::ORIGIN)).unwrap();
+                sender.send(Command::Quit).unwrap();
+                return Ok(None);
+            }
+            typed = true;
+            Ok(Some(key('g')))
+        };
+        run_app(
+            &mut terminal,
+            &mut tabs,
+            &Theme::UNICODE,
+            &mut read_key,
+            &commands,
+        )?;
+
+        let board = &tabs[0].board;
+        assert_eq!(
+            board.past_record(),
+            [
+                (Point::ORIGIN, Stone::Black),
+                (Point::new(0, 1), Stone::White),
+                (Point::PASS, Stone::White),
+            ]
+        );
+        assert!(!tabs[0].saved);
+        let status: String = (0..40)
+            .map(|x| terminal.backend().buffer().get(x, 11).symbol.as_str())
+            .collect();
+        assert!(status.contains("Illegal move: (0, 0) is occupied"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_command() {
+        assert!(matches!(
+            parse_command("place J10"),
+            Some(Command::Place(Point::ORIGIN))
+        ));
+        assert!(matches!(
+            parse_command(" place (3, -2)\n"),
+            Some(Command::Place(Point { x: 3, y: -2 }))
+        ));
+        assert!(matches!(parse_command("pass"), Some(Command::Pass)));
+        assert!(matches!(parse_command("undo"), Some(Command::Undo)));
+        assert!(matches!(parse_command("quit"), Some(Command::Quit)));
+        for line in ["", "place", "place Z99x", "pass now", "resign"] {
+            assert!(parse_command(line).is_none());
+        }
+    }
+
+    #[test]
+    fn test_send_commands() -> Result<(), Box<dyn Error>> {
+        let path = env::temp_dir().join(format!("c6-commands-{}.txt", std::process::id()));
+        fs::write(&path, "place J10\nhello\npass\nquit\n")?;
+        let (sender, commands) = mpsc::channel();
+        send_commands(path.clone(), sender);
+        let received: Vec<_> = commands.iter().collect();
+        fs::remove_file(&path)?;
+        assert!(matches!(
+            received[..],
+            [Command::Place(Point::ORIGIN), Command::Pass, Command::Quit]
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_minimap() -> Result<(), Box<dyn Error>> {
+        let mut board = Board::new_infinite();
+        let area = Rect::new(0, 0, 41, 14);
+        let render = |board: &Board| {
+            let mut buf = Buffer::empty(area);
+            let mut term_center = Point::ORIGIN;
+            BoardView {
+                theme: &Theme::UNICODE,
+                board,
+                term_center: &mut term_center,
+                cursor: Point::ORIGIN,
+                flash: false,
+                preview: None,
+                minimap: true,
+                threats: [&[], &[]],
+                messages: &[],
+            }
+            .render(area, &mut buf);
+            buf
+        };
+
+        // No minimap for an empty board.
+        let buf = render(&board);
+        assert!(buf.content.iter().all(|cell| cell.symbol != "┌"));
+
+        // The viewport spans [-10, 9] by [-7, 6], so the minimap covers
+        // [-10, 60] by [-7, 30] with 5 by 10 points in each cell.
+        board.set(Point::ORIGIN, Stone::Black)?;
+        board.set(Point::new(60, 30), Stone::White)?;
+        let buf = render(&board);
+        assert_eq!(buf.get(24, 0).symbol, "┌");
+        assert_eq!(buf.get(40, 5).symbol, "┘");
+
+        let black = buf.get(27, 1);
+        assert_eq!(black.symbol, "●");
+        assert!(black.modifier.contains(Modifier::REVERSED));
+        let white = buf.get(39, 4);
+        assert_eq!(white.symbol, "○");
+        assert!(!white.modifier.contains(Modifier::REVERSED));
+        assert_eq!(buf.get(28, 3).symbol, "·");
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_ascii() -> Result<(), Box<dyn Error>> {
+        let mut board = Board::new_infinite();
+        board.set(Point::ORIGIN, Stone::Black)?;
+        board.set(Point::new(1, 0), Stone::White)?;
+        let area = Rect::new(0, 0, 11, 4);
+        let mut buf = Buffer::empty(area);
+        let mut term_center = Point::ORIGIN;
+        BoardView {
+            theme: &Theme::ASCII,
+            board: &board,
+            term_center: &mut term_center,
+            cursor: Point::ORIGIN,
+            flash: false,
+            preview: None,
+            minimap: false,
+            threats: [&[], &[]],
+            messages: &[],
+        }
+        .render(area, &mut buf);
+
+        let rows: Vec<String> = (0..4)
+            .map(|y| (0..11).map(|x| buf.get(x, y).symbol.as_str()).collect())
+            .collect();
+        assert_eq!(rows[1], " . . . . . ");
+        assert_eq!(rows[2], " . .(X)O`. ");
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_colorblind() -> Result<(), Box<dyn Error>> {
+        let mut board = Board::new_infinite();
+        board.set(Point::ORIGIN, Stone::Black)?;
+        board.set(Point::new(1, 0), Stone::White)?;
+        let area = Rect::new(0, 0, 11, 4);
+        let mut buf = Buffer::empty(area);
+        let mut term_center = Point::ORIGIN;
+        BoardView {
+            theme: &Theme::COLORBLIND,
+            board: &board,
+            term_center: &mut term_center,
+            cursor: Point::ORIGIN,
+            flash: false,
+            preview: None,
+            minimap: false,
+            threats: [&[], &[]],
+            messages: &[],
+        }
+        .render(area, &mut buf);
+
+        // The stones differ in shape, not only in color.
+        let (black, white) = (buf.get(5, 2), buf.get(7, 2));
+        assert_eq!(black.symbol, "●");
+        assert_eq!(white.symbol, "◇");
+        assert_eq!(black.fg, Color::Reset);
+        assert_eq!(white.fg, Color::Rgb(86, 180, 233));
+        let stone_colors = Theme::COLORBLIND.stone_colors;
+        for color in Theme::COLORBLIND.threat_colors {
+            assert!(stone_colors
+                .iter()
+                .all(|(_, &stone_color)| stone_color != color));
         }
+        assert!(Theme::by_name("colorblind").is_some_and(|theme| theme.white == '◇'));
+        assert!(Theme::by_name("sepia").is_none());
+        Ok(())
     }
 }
@@ -0,0 +1,35 @@
+use crate::{Board, Point};
+
+impl Board {
+    /// Writes the past record as a numbered list of turns, with points in
+    /// algebraic notation where possible, e.g. `1. J10 / 2. K10 K11 3. I9 L11`.
+    ///
+    /// The turns are those of [`Board::iter_turns`], and the single stone
+    /// of the first turn is set apart with a slash. Passes are written as
+    /// `pass`, and points outside a 19 by 19 board as `(x, y)`.
+    pub fn to_notation(&self) -> String {
+        let mut notation = String::new();
+        for (i, turn) in self.iter_turns().enumerate() {
+            match i {
+                0 => {}
+                1 => notation.push_str(" / "),
+                _ => notation.push(' '),
+            }
+            notation.push_str(&format!("{}.", i + 1));
+            for &(point, _) in turn {
+                notation.push(' ');
+                notation.push_str(&move_text(point));
+            }
+        }
+        notation
+    }
+}
+
+// Writes a point of the record in notation.
+fn move_text(point: Point) -> String {
+    if point == Point::PASS {
+        "pass".into()
+    } else {
+        point.to_algebraic().unwrap_or_else(|| point.to_string())
+    }
+}
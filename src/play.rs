@@ -0,0 +1,137 @@
+#[cfg(feature = "rand")]
+use rand::Rng;
+
+use crate::{Board, Outcome, Point, ScoreWeights, SetError, Stone};
+
+/// A strategy choosing the moves of one side, as played by [`run_game`].
+pub trait MoveGen {
+    /// Chooses the stones to place for a turn of `stone` on `board`.
+    ///
+    /// Either point may be [`Point::PASS`], which ends the turn, so that a
+    /// point after it is not placed. Only the first point is placed if there
+    /// is a single stone left in the turn, as in black's opening.
+    fn choose(&mut self, board: &Board, stone: Stone) -> (Point, Point);
+}
+
+// The point to open with on a board with no stones to play around.
+fn opening_point(board: &Board) -> Point {
+    if board.can_place(Point::ORIGIN).is_ok() {
+        Point::ORIGIN
+    } else {
+        Point::PASS
+    }
+}
+
+/// Plays at random among the [`Board::candidate_moves`], or at the origin
+/// on an empty board.
+#[cfg(feature = "rand")]
+#[derive(Debug, Clone)]
+pub struct RandomMoveGen<R> {
+    rng: R,
+}
+
+#[cfg(feature = "rand")]
+impl<R: Rng> RandomMoveGen<R> {
+    pub fn new(rng: R) -> RandomMoveGen<R> {
+        RandomMoveGen { rng }
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<R: Rng> MoveGen for RandomMoveGen<R> {
+    fn choose(&mut self, board: &Board, _stone: Stone) -> (Point, Point) {
+        let mut candidates = board.candidate_moves();
+        if candidates.is_empty() {
+            return (opening_point(board), Point::PASS);
+        }
+        let mut pick = || match candidates.len() {
+            0 => Point::PASS,
+            len => candidates.swap_remove(self.rng.gen_range(0..len)),
+        };
+        (pick(), pick())
+    }
+}
+
+/// Places each stone in turn where it maximizes [`Board::score`], among the
+/// [`Board::candidate_moves`], or at the origin on an empty board.
+///
+/// Ties go to the candidate with the smallest index.
+#[derive(Debug, Clone, Default)]
+pub struct GreedyMoveGen {
+    pub weights: ScoreWeights,
+}
+
+impl GreedyMoveGen {
+    pub fn new(weights: ScoreWeights) -> GreedyMoveGen {
+        GreedyMoveGen { weights }
+    }
+
+    fn best(&self, board: &mut Board, stone: Stone) -> Point {
+        let mut best = None;
+        for point in board.candidate_moves() {
+            if board.set(point, stone).is_err() {
+                continue;
+            }
+            let score = board.score(stone, &self.weights);
+            board.unset();
+            if best.map_or(true, |(_, best_score)| score > best_score) {
+                best = Some((point, score));
+            }
+        }
+        best.map_or_else(|| opening_point(board), |(point, _)| point)
+    }
+}
+
+impl MoveGen for GreedyMoveGen {
+    fn choose(&mut self, board: &Board, stone: Stone) -> (Point, Point) {
+        // Undoing on a copy must not be stopped by the undo limit.
+        let mut board = board.clone();
+        board.undo_limit = None;
+
+        let first = self.best(&mut board, stone);
+        if first == Point::PASS || board.set(first, stone).is_err() {
+            return (first, Point::PASS);
+        }
+        if board.is_terminal() {
+            return (first, Point::PASS);
+        }
+        (first, self.best(&mut board, stone))
+    }
+}
+
+/// Plays a game on `board` from its current position, with `black` and
+/// `white` choosing the moves of each side, for up to `max_turns` turns.
+///
+/// Returns the outcome of the game, or `None` if it has not ended within
+/// the turns. Fails if a strategy chooses a point a stone can't be placed
+/// at.
+pub fn run_game(
+    board: &mut Board,
+    black: &mut dyn MoveGen,
+    white: &mut dyn MoveGen,
+    max_turns: usize,
+) -> Result<Option<Outcome>, SetError> {
+    for _ in 0..max_turns {
+        if board.is_terminal() {
+            break;
+        }
+        let turn = board.turn();
+        let gen: &mut dyn MoveGen = match turn.stone {
+            Stone::Black => black,
+            Stone::White => white,
+        };
+        let (first, second) = gen.choose(board, turn.stone);
+        for point in [first, second].into_iter().take(turn.remaining as usize) {
+            if point == Point::PASS {
+                // Passing hands the turn to the other side.
+                board.pass();
+                break;
+            }
+            board.set(point, turn.stone)?;
+            if board.is_terminal() {
+                break;
+            }
+        }
+    }
+    Ok(board.cached_outcome())
+}
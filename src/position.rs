@@ -0,0 +1,187 @@
+use std::io::{self, prelude::*};
+
+use crate::{
+    record::crc24, Board, Bounds, Outcome, Point, RawBoard, Stone, Turn, WinRule, SLOT_INDEX_BITS,
+    WORDS_PER_CHUNK, WORD_INDEX_BITS,
+};
+
+const MAGIC: &[u8; 4] = b"C6P\x01";
+
+#[derive(Debug, thiserror::Error)]
+pub enum LoadPositionError {
+    #[error("io failure: {0}")]
+    Io(#[from] io::Error),
+    #[error("corrupted data: {0}")]
+    Data(&'static str),
+}
+
+// A cursor over the bytes of a saved position, failing on truncated data.
+struct Bytes<'a>(&'a [u8]);
+
+impl Bytes<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], LoadPositionError> {
+        if self.0.len() < N {
+            return Err(LoadPositionError::Data("unexpected end of data"));
+        }
+        let (bytes, rest) = self.0.split_at(N);
+        self.0 = rest;
+        Ok(bytes.try_into().unwrap())
+    }
+
+    fn u32(&mut self) -> Result<u32, LoadPositionError> {
+        self.take().map(u32::from_le_bytes)
+    }
+
+    fn u64(&mut self) -> Result<u64, LoadPositionError> {
+        self.take().map(u64::from_le_bytes)
+    }
+}
+
+impl Board {
+    /// Writes the current position, without its history, by dumping the
+    /// chunks of the board as they are stored.
+    ///
+    /// This is read back by [`Board::load_position`] in time proportional to
+    /// the number of chunks, with no moves to replay. Besides the stones,
+    /// only the bounds, the win rule and the turn are kept.
+    ///
+    /// The layout is the magic `C6P\x01`, followed by the bounds as a tag
+    /// byte (0 for infinite, 1 for a rect, 2 for limited) and their `u32`
+    /// sizes, the win rule byte, the stone to play and the number of stones
+    /// it has left, the number of chunks as a `u64`, each chunk as its index
+    /// and words, and finally the CRC-24 of all that in 4 bytes. Integers
+    /// are little-endian.
+    pub fn save_position<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let mut buf = MAGIC.to_vec();
+        match self.bounds {
+            Bounds::Infinite => buf.push(0),
+            Bounds::Rect(width, height) => {
+                buf.push(1);
+                buf.extend(width.to_le_bytes());
+                buf.extend(height.to_le_bytes());
+            }
+            Bounds::Limited(max) => {
+                buf.push(2);
+                buf.extend(max.to_le_bytes());
+            }
+        }
+        buf.push(self.rule as u8);
+        let Turn { stone, remaining } = self.turn();
+        buf.extend([stone as u8, remaining]);
+
+        let chunks: Vec<_> = self
+            .board
+            .iter_chunks()
+            .filter(|(_, words)| words.iter().any(|&w| w != 0))
+            .collect();
+        buf.extend((chunks.len() as u64).to_le_bytes());
+        for (chunk_i, words) in chunks {
+            buf.extend(chunk_i.to_le_bytes());
+            for word in words {
+                buf.extend(word.to_le_bytes());
+            }
+        }
+
+        let crc = crc24(&buf);
+        buf.extend(crc.to_le_bytes());
+        writer.write_all(&buf)
+    }
+
+    /// Reads a position written by [`Board::save_position`].
+    ///
+    /// The record of the loaded board is synthetic, with the stones placed
+    /// in the order of their indexes, as no history is saved. A win found on
+    /// the board is taken to be made by the last of them.
+    pub fn load_position<R: Read>(mut reader: R) -> Result<Board, LoadPositionError> {
+        use LoadPositionError::*;
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        if buf.len() < 4 {
+            return Err(Data("unexpected end of data"));
+        }
+        let (data, crc) = buf.split_at(buf.len() - 4);
+        if crc24(data) != u32::from_le_bytes(crc.try_into().unwrap()) {
+            return Err(Data("wrong checksum"));
+        }
+
+        let mut bytes = Bytes(data);
+        if &bytes.take()? != MAGIC {
+            return Err(Data("not a saved position"));
+        }
+        let bounds = match bytes.take::<1>()? {
+            [0] => Bounds::Infinite,
+            [1] => Bounds::Rect(bytes.u32()?, bytes.u32()?),
+            [2] => Bounds::Limited(bytes.u32()?),
+            _ => return Err(Data("invalid bounds")),
+        };
+        let rule = match bytes.take()? {
+            [0] => WinRule::SixOrMore,
+            [1] => WinRule::ExactlySix,
+            _ => return Err(Data("invalid win rule")),
+        };
+        let turn = match bytes.take()? {
+            [stone @ (0 | 1), remaining @ (1 | 2)] => Turn {
+                stone: if stone == 0 {
+                    Stone::Black
+                } else {
+                    Stone::White
+                },
+                remaining,
+            },
+            _ => return Err(Data("invalid turn")),
+        };
+
+        let count = bytes.u64()?;
+        let mut chunks = Vec::new();
+        let mut record = Vec::new();
+        let mut last_i = None;
+        for _ in 0..count {
+            let chunk_i = bytes.u64()?;
+            if last_i.is_some_and(|last_i| last_i >= chunk_i) {
+                return Err(Data("chunks out of order"));
+            }
+            if chunk_i >> (64 - WORD_INDEX_BITS - SLOT_INDEX_BITS) != 0 {
+                return Err(Data("invalid chunk index"));
+            }
+            last_i = Some(chunk_i);
+
+            let mut words = [0; WORDS_PER_CHUNK];
+            for (word_i, word) in words.iter_mut().enumerate() {
+                *word = bytes.u64()?;
+                for slot_i in 0..64 / 2 {
+                    let stone = match (*word >> (slot_i * 2)) & 3 {
+                        0 => continue,
+                        1 => Stone::Black,
+                        2 => Stone::White,
+                        _ => return Err(Data("invalid slot")),
+                    };
+                    let i =
+                        (chunk_i << WORD_INDEX_BITS | word_i as u64) << SLOT_INDEX_BITS | slot_i;
+                    let point = Point::from_index(i);
+                    if !bounds.contains(point) {
+                        return Err(Data("stone out of bounds"));
+                    }
+                    record.push((point, stone));
+                }
+            }
+            chunks.push((chunk_i, words));
+        }
+        if !bytes.0.is_empty() {
+            return Err(Data("trailing data"));
+        }
+
+        let mut board = Board::new(bounds);
+        board.board = RawBoard::from_chunks(chunks);
+        board.times = vec![None; record.len()];
+        board.index = record.len();
+        board.record = record;
+        board.turn = Some(turn);
+        board.rule = rule;
+        board.occupied = board.scan_occupied_bounds();
+        if let Some(win @ Outcome::Win { .. }) = board.outcome() {
+            board.win = Some((board.index, win));
+        }
+        Ok(board)
+    }
+}
@@ -0,0 +1,76 @@
+use rand::Rng;
+
+use crate::{limited_range, zigzag_range, Board, Bounds, Point};
+
+// Returns the top-left and bottom-right corners of the bounds, or `None`
+// if they contain no points.
+fn corners(bounds: Bounds) -> Option<(Point, Point)> {
+    let (x_min, x_max, y_min, y_max) = match bounds {
+        Bounds::Infinite => (i32::MIN, i32::MAX, i32::MIN, i32::MAX),
+        Bounds::Rect(0, _) | Bounds::Rect(_, 0) => return None,
+        Bounds::Rect(width, height) => {
+            let (x_min, x_max) = zigzag_range(width);
+            let (y_min, y_max) = zigzag_range(height);
+            (x_min, x_max, y_min, y_max)
+        }
+        Bounds::Limited(max) => {
+            let (min, max) = limited_range(max);
+            (min, max, min, max)
+        }
+    };
+    Some((Point::new(x_min, y_min), Point::new(x_max, y_max)))
+}
+
+impl Board {
+    /// Plays up to `moves` stones at random empty points within `area`,
+    /// taking turns as usual, and returns the number of stones placed.
+    ///
+    /// This stops early once there are no empty points left in the area
+    /// and on the board. An infinite `area` is narrowed down to the bounds
+    /// of the board. Otherwise, the empty points of the area on the board
+    /// are enumerated up front, so the area should be kept small unless
+    /// both it and the board are infinite.
+    pub fn play_random(&mut self, rng: &mut impl Rng, area: Bounds, moves: usize) -> usize {
+        let area = match area {
+            Bounds::Infinite => self.bounds,
+            area => area,
+        };
+
+        let mut empty: Vec<Point> = match (corners(area), corners(self.bounds)) {
+            _ if area == Bounds::Infinite => Vec::new(),
+            (Some((a_min, a_max)), Some((b_min, b_max))) => {
+                let min = Point::new(a_min.x.max(b_min.x), a_min.y.max(b_min.y));
+                let max = Point::new(a_max.x.min(b_max.x), a_max.y.min(b_max.y));
+                if min.x > max.x || min.y > max.y {
+                    Vec::new()
+                } else {
+                    let size = (
+                        max.x.abs_diff(min.x).saturating_add(1),
+                        max.y.abs_diff(min.y).saturating_add(1),
+                    );
+                    self.region(min, size, true)
+                        .filter(|&(_, slot)| slot.is_none())
+                        .map(|(point, _)| point)
+                        .collect()
+                }
+            }
+            _ => Vec::new(),
+        };
+
+        for placed in 0..moves {
+            let point = match area {
+                // The whole coordinate space is practically never full.
+                Bounds::Infinite => loop {
+                    let point = Point::new(rng.gen(), rng.gen());
+                    if self.bounds.contains(point) && self.is_empty_at(point) {
+                        break point;
+                    }
+                },
+                _ if empty.is_empty() => return placed,
+                _ => empty.swap_remove(rng.gen_range(0..empty.len())),
+            };
+            let _ = self.set(point, self.turn().stone);
+        }
+        moves
+    }
+}
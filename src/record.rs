@@ -1,8 +1,11 @@
-use std::io::{self, prelude::*};
+use std::{
+    io::{self, prelude::*},
+    time::Duration,
+};
 
 use base64::{prelude::*, DecodeError, DecodeSliceError};
 
-use crate::{Board, Bounds, Point, SetError, Stone};
+use crate::{Board, Bounds, Outcome, Point, SetError, Stone, Turn, WinRule};
 
 fn write_var_u65(buf: &mut Vec<u8>, hi_64: u64, lo_1: u8) {
     let mut var_buf = [0; 10];
@@ -55,6 +58,14 @@ fn read_var_u65(buf: &mut &[u8]) -> Option<(u64, u8)> {
 }
 
 const HEADER_LINE: &str = "-----BEGIN CONNECT6 RECORD-----";
+// The version of the layout of a record, to be bumped on incompatible
+// changes. Records without a `Format` header are of version 1.
+//
+// Version 2 added the `Rule`, `Times` and `Opening` headers, segments and
+// move times in the lines format. Records are written with the oldest
+// version that holds their headers, so that older readers still accept
+// the ones that don't need it.
+const FORMAT_VERSION: u32 = 2;
 const VERSION_LINE: &str = concat!(
     "Version: ",
     env!("CARGO_PKG_NAME"),
@@ -62,10 +73,19 @@ const VERSION_LINE: &str = concat!(
     env!("CARGO_PKG_VERSION")
 );
 const TAIL_LINE: &str = "-----END CONNECT6 RECORD-----";
+const SEGMENT_HEADER_LINE: &str = "-----BEGIN CONNECT6 SEGMENT-----";
+const SEGMENT_TAIL_LINE: &str = "-----END CONNECT6 SEGMENT-----";
+const LINES_HEADER_LINE: &str = "-----BEGIN CONNECT6 MOVES-----";
+const LINES_TAIL_LINE: &str = "-----END CONNECT6 MOVES-----";
 
 struct LineReader<R> {
     reader: R,
     buf: String,
+    // The 1-based number of the line last read, or of the line after the
+    // end when the end is reached.
+    line: usize,
+    // Whether the line last read is to be read again.
+    unread: bool,
 }
 
 impl<R: BufRead> LineReader<R> {
@@ -73,10 +93,17 @@ impl<R: BufRead> LineReader<R> {
         Self {
             reader,
             buf: String::new(),
+            line: 0,
+            unread: false,
         }
     }
 
     fn read_line(&mut self) -> io::Result<Option<&str>> {
+        self.line += 1;
+        if self.unread {
+            self.unread = false;
+            return Ok(Some(&self.buf[..]));
+        }
         self.buf.clear();
         if self.reader.read_line(&mut self.buf)? == 0 {
             return Ok(None);
@@ -90,11 +117,17 @@ impl<R: BufRead> LineReader<R> {
         }
         Ok(Some(&self.buf[..]))
     }
+
+    // Makes the next `read_line` return the line last read again.
+    fn unread(&mut self) {
+        self.unread = true;
+        self.line -= 1;
+    }
 }
 
 // Stolen from OpenPGP spec:
 // https://www.rfc-editor.org/rfc/rfc4880#section-6.1
-fn crc24(bytes: &[u8]) -> u32 {
+pub(crate) fn crc24(bytes: &[u8]) -> u32 {
     const CRC24_INIT: u32 = 0xb704ce;
     const CRC24_POLY: u32 = 0x1864cfb;
 
@@ -111,150 +144,890 @@ fn crc24(bytes: &[u8]) -> u32 {
     crc & 0xffffff
 }
 
-fn parse_bounds(mut s: &str) -> Option<Bounds> {
+pub(crate) fn format_bounds(bounds: Bounds) -> String {
+    match bounds {
+        Bounds::Infinite => "Infinite".into(),
+        Bounds::Rect(x, y) => format!("Rect({x}*{y})"),
+        Bounds::Limited(max) => format!("Limited({max})"),
+    }
+}
+
+pub(crate) fn parse_bounds(mut s: &str) -> Option<Bounds> {
     if s == "Infinite" {
         return Some(Bounds::Infinite);
     }
+    if let Some(max) = s.strip_prefix("Limited(") {
+        return Some(Bounds::Limited(max.strip_suffix(')')?.parse().ok()?));
+    }
     s = s.strip_prefix("Rect(")?.strip_suffix(')')?;
     let (x, y) = s.split_once('*')?;
     Some(Bounds::Rect(x.parse().ok()?, y.parse().ok()?))
 }
 
+pub(crate) fn parse_turn(s: &str) -> Option<Turn> {
+    let (stone, remaining) = s.strip_suffix(')')?.split_once('(')?;
+    let stone = stone.parse().ok()?;
+    match remaining.parse() {
+        Ok(remaining @ (1 | 2)) => Some(Turn { stone, remaining }),
+        _ => None,
+    }
+}
+
+pub(crate) fn parse_result(s: &str) -> Option<Stone> {
+    s.strip_prefix("Resign(")?.strip_suffix(')')?.parse().ok()
+}
+
+// Encodes the times as varints of milliseconds, with the low bit set for
+// the moves that are timed, returning `None` if none are.
+fn format_times(times: &[Option<Duration>]) -> Option<String> {
+    if times.iter().all(Option::is_none) {
+        return None;
+    }
+    let mut buf = Vec::new();
+    for time in times {
+        match time {
+            Some(time) => write_var_u65(&mut buf, time.as_millis() as u64, 1),
+            None => write_var_u65(&mut buf, 0, 0),
+        }
+    }
+    Some(BASE64_STANDARD.encode(buf))
+}
+
+fn parse_times(s: &str) -> Option<Vec<Option<Duration>>> {
+    let buf = BASE64_STANDARD.decode(s).ok()?;
+    let mut rest = &buf[..];
+    let mut times = Vec::new();
+    while !rest.is_empty() {
+        let (millis, timed) = read_var_u65(&mut rest)?;
+        times.push((timed == 1).then(|| Duration::from_millis(millis)));
+    }
+    Some(times)
+}
+
+pub(crate) fn parse_rule(s: &str) -> Option<WinRule> {
+    match s {
+        "SixOrMore" => Some(WinRule::SixOrMore),
+        "ExactlySix" => Some(WinRule::ExactlySix),
+        _ => None,
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum LoadRecordError {
     #[error("io failure: {0}")]
     Io(#[from] io::Error),
-    #[error("syntax error: {0}")]
-    Syntax(&'static str),
-    #[error("unable to decode base64: {0}")]
-    Base64(#[from] DecodeError),
-    #[error("corrupted data: {0}")]
-    Data(&'static str),
-    #[error("unable to set on board: {0}")]
-    Set(#[from] SetError),
+    #[error("line {line}: syntax error: {msg}")]
+    Syntax { line: usize, msg: &'static str },
+    #[error("line {line}: unable to decode base64: {source}")]
+    Base64 { line: usize, source: DecodeError },
+    #[error("line {line}: corrupted data: {msg}")]
+    Data { line: usize, msg: &'static str },
+    #[error("line {line}: unsupported format version: {version}")]
+    UnsupportedFormat { line: usize, version: u32 },
+    /// The move at `index` in the record, counting from zero, is illegal.
+    #[error("move {index} at {point}: unable to set on board: {source}")]
+    Set {
+        index: usize,
+        point: Point,
+        source: SetError,
+    },
 }
 
-impl Board {
-    pub fn save_record<W: Write>(&self, mut writer: W) -> io::Result<()> {
-        writeln!(writer, "{HEADER_LINE}")?;
-        writeln!(writer, "{VERSION_LINE}")?;
-        match self.bounds {
-            Bounds::Infinite => {
-                writeln!(writer, "Board: Infinite")?;
-            }
-            Bounds::Rect(x, y) => {
-                writeln!(writer, "Board: Rect({x}*{y})")?;
-            }
+/// An error from [`Board::from_position_string`].
+#[derive(Debug, thiserror::Error)]
+#[error("invalid position string")]
+pub struct ParsePositionError;
+
+/// Options for [`Board::save_record_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SaveOptions {
+    /// Whether to write the `Version` header, which names the version of
+    /// this crate. Turn it off for output that doesn't change across versions.
+    pub version: bool,
+    /// The number of base64 characters per line, rounded down to a multiple
+    /// of 4 (but at least 4).
+    pub line_width: usize,
+    /// Whether to write the CRC-24 checksum line.
+    pub crc: bool,
+    /// Whether to write the moves in the URL-safe base64 alphabet, with `-`
+    /// and `_` in place of `+` and `/`. The checksum line is unaffected.
+    pub url_safe: bool,
+}
+
+impl Default for SaveOptions {
+    fn default() -> SaveOptions {
+        SaveOptions {
+            version: true,
+            line_width: 64,
+            crc: true,
+            url_safe: false,
         }
-        writeln!(writer, "Count: {}", self.index())?;
-        writeln!(writer)?;
+    }
+}
 
-        let mut buf = Vec::new();
-        for &(point, stone) in self.past_record() {
-            write_var_u65(&mut buf, point.index(), stone as u8);
+/// Options for [`Board::load_record_lenient`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoadOptions {
+    /// Whether to reject a record whose checksum line doesn't match.
+    pub verify_crc: bool,
+    /// Whether to reject a record whose `Count` header doesn't match the
+    /// number of moves.
+    pub enforce_count: bool,
+}
+
+impl Default for LoadOptions {
+    fn default() -> LoadOptions {
+        LoadOptions {
+            verify_crc: true,
+            enforce_count: true,
         }
+    }
+}
 
-        let mut b64_buf = [0; 64];
-        for chunk in buf.chunks(48) {
-            let len = BASE64_STANDARD.encode_slice(chunk, &mut b64_buf).unwrap();
-            writer.write_all(&b64_buf[..len])?;
-            writeln!(writer)?;
+/// The headers of a record, as read by [`Board::read_header`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RecordHeader {
+    pub bounds: Bounds,
+    /// The number of moves in the record, if given.
+    pub count: Option<usize>,
+    /// The turn set explicitly, if any.
+    pub turn: Option<Turn>,
+    /// The stone that resigned, if any.
+    pub resigned: Option<Stone>,
+    /// The rule deciding which lines win, which defaults to six or more.
+    pub rule: WinRule,
+    /// Whether the sides were swapped after the opening stone, which is
+    /// then white.
+    pub swapped: bool,
+    /// The time taken by each move, if the record is timed.
+    pub times: Vec<Option<Duration>>,
+    /// The other headers in the order they appear, such as `Version`.
+    pub metadata: Vec<(String, String)>,
+}
+
+// The line numbers of the headers checked after the moves are read, and
+// the `From` header of a segment.
+#[derive(Default)]
+struct HeaderLines {
+    count: Option<usize>,
+    times: Option<usize>,
+    rule: Option<usize>,
+    opening: Option<usize>,
+    from: Option<(usize, usize)>,
+}
+
+// Reads the lines up to and including the blank line after the headers.
+fn read_header_lines<R: BufRead>(
+    reader: &mut LineReader<R>,
+) -> Result<(RecordHeader, HeaderLines), LoadRecordError> {
+    if reader.read_line()?.map(str::trim_end) != Some(HEADER_LINE) {
+        return Err(LoadRecordError::Syntax {
+            line: reader.line,
+            msg: "expected header line",
+        });
+    }
+    read_header_fields(reader)
+}
+
+// Same as `read_header_lines`, but starts after the header line.
+fn read_header_fields<R: BufRead>(
+    reader: &mut LineReader<R>,
+) -> Result<(RecordHeader, HeaderLines), LoadRecordError> {
+    use LoadRecordError::*;
+
+    let syntax = |line, msg| Syntax { line, msg };
+
+    let mut header = RecordHeader::default();
+    let mut lines = HeaderLines::default();
+    loop {
+        let line_no = reader.line + 1;
+        let line = reader
+            .read_line()?
+            .ok_or(syntax(line_no, "unexpected EOF"))?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
         }
 
+        // The blank line may be left out before the moves, since no line of
+        // base64, checksum or tail has a colon in it.
+        let Some((key, value)) = line.split_once(':') else {
+            reader.unread();
+            break;
+        };
+        let value = value.trim_start();
+        match key {
+            "Format" => match value.parse::<u32>() {
+                Ok(1..=FORMAT_VERSION) => {}
+                Ok(version) => {
+                    return Err(UnsupportedFormat {
+                        line: line_no,
+                        version,
+                    })
+                }
+                Err(_) => return Err(syntax(line_no, "invalid header: Format")),
+            },
+            "Board" => {
+                header.bounds =
+                    parse_bounds(value).ok_or(syntax(line_no, "invalid header: Board"))?;
+            }
+            "Count" => match value.parse::<usize>() {
+                Ok(res) => {
+                    header.count = Some(res);
+                    lines.count = Some(line_no);
+                }
+                Err(_) => return Err(syntax(line_no, "invalid header: Count")),
+            },
+            "From" => match value.parse::<usize>() {
+                Ok(res) => lines.from = Some((res, line_no)),
+                Err(_) => return Err(syntax(line_no, "invalid header: From")),
+            },
+            "Turn" => {
+                header.turn =
+                    Some(parse_turn(value).ok_or(syntax(line_no, "invalid header: Turn"))?);
+            }
+            "Result" => {
+                header.resigned =
+                    Some(parse_result(value).ok_or(syntax(line_no, "invalid header: Result"))?);
+            }
+            "Times" => {
+                header.times =
+                    parse_times(value).ok_or(syntax(line_no, "invalid header: Times"))?;
+                lines.times = Some(line_no);
+            }
+            "Rule" => {
+                header.rule = parse_rule(value).ok_or(syntax(line_no, "invalid header: Rule"))?;
+                lines.rule = Some(line_no);
+            }
+            "Opening" => {
+                if value != "Swapped" {
+                    return Err(syntax(line_no, "invalid header: Opening"));
+                }
+                header.swapped = true;
+                lines.opening = Some(line_no);
+            }
+            _ => header.metadata.push((key.into(), value.into())),
+        }
+    }
+    Ok((header, lines))
+}
+
+fn write_moves<W: Write>(
+    writer: &mut W,
+    moves: &[(Point, Stone)],
+    options: &SaveOptions,
+) -> io::Result<()> {
+    let mut buf = Vec::new();
+    for &(point, stone) in moves {
+        write_var_u65(&mut buf, point.index(), stone as u8);
+    }
+
+    let engine = if options.url_safe {
+        &BASE64_URL_SAFE
+    } else {
+        &BASE64_STANDARD
+    };
+    let bytes_per_line = (options.line_width / 4).max(1) * 3;
+    for chunk in buf.chunks(bytes_per_line) {
+        writeln!(writer, "{}", engine.encode(chunk))?;
+    }
+
+    if options.crc {
         // OpenPGP uses BE, so we use LE here, for a change.
         let crc = crc24(&buf).to_le_bytes();
-        BASE64_STANDARD
-            .encode_slice(&crc[..3], &mut b64_buf[1..])
-            .unwrap();
-        b64_buf[0] = b'=';
-        b64_buf[5] = b'\n';
-        writer.write_all(&b64_buf[..6])?;
+        writeln!(writer, "={}", BASE64_STANDARD.encode(&crc[..3]))?;
+    }
+    Ok(())
+}
+
+// Reads the lines of data up to and including the tail line, and plays the
+// moves in them on the board, returning the number of moves.
+fn read_moves<R: BufRead>(
+    reader: &mut LineReader<R>,
+    tail_line: &str,
+    options: &LoadOptions,
+    board: &mut Board,
+) -> Result<usize, LoadRecordError> {
+    use LoadRecordError::*;
+
+    let syntax = |line, msg| Syntax { line, msg };
+
+    let mut rec_buf = Vec::new();
+    // The offset in `rec_buf` at which each line of data starts, along
+    // with its line number, for locating malformed varints.
+    let mut line_starts = Vec::new();
+    let mut crc = None;
+    loop {
+        let line_no = reader.line + 1;
+        let line = reader
+            .read_line()?
+            .ok_or(syntax(line_no, "unexpected EOF"))?
+            .trim();
+        if line == tail_line {
+            break;
+        }
+        if line.is_empty() {
+            continue;
+        }
+        if crc.is_some() {
+            return Err(syntax(line_no, "expected tail line"));
+        }
+
+        let Some(crc_b64) = line.strip_prefix('=') else {
+            line_starts.push((rec_buf.len(), line_no));
+            // Either alphabet may be used, and the lines that are the same
+            // in both decode the same either way.
+            let engine = if line.contains(['-', '_']) {
+                &BASE64_URL_SAFE
+            } else {
+                &BASE64_STANDARD
+            };
+            engine
+                .decode_vec(line, &mut rec_buf)
+                .map_err(|source| Base64 {
+                    line: line_no,
+                    source,
+                })?;
+            continue;
+        };
+        if crc_b64.len() != 4 {
+            return Err(syntax(line_no, "expected checksum"));
+        }
+        let mut crc_buf = [0; 4];
+        match BASE64_STANDARD.decode_slice(crc_b64, &mut crc_buf) {
+            Ok(_) => crc = Some((u32::from_le_bytes(crc_buf), line_no)),
+            Err(DecodeSliceError::DecodeError(source)) => {
+                return Err(Base64 {
+                    line: line_no,
+                    source,
+                })
+            }
+            Err(DecodeSliceError::OutputSliceTooSmall) => unreachable!(),
+        }
+    }
+
+    // Records saved without a checksum are accepted as is.
+    if let Some((crc, line)) = crc.filter(|_| options.verify_crc) {
+        if crc != crc24(&rec_buf) {
+            return Err(Data {
+                line,
+                msg: "wrong checksum",
+            });
+        }
+    }
+
+    let mut rest = &rec_buf[..];
+    let mut count = 0;
+    while !rest.is_empty() {
+        let offset = rec_buf.len() - rest.len();
+        let Some((point_i, stone_i)) = read_var_u65(&mut rest) else {
+            let i = line_starts.partition_point(|&(start, _)| start <= offset);
+            return Err(Data {
+                line: line_starts[i - 1].1,
+                msg: "malformed varint",
+            });
+        };
+
+        let point = Point::from_index(point_i);
+        let stone = match stone_i {
+            0 => Stone::Black,
+            _ => Stone::White,
+        };
+
+        if point == Point::PASS {
+            board.push_pass(stone);
+        } else {
+            let index = board.index();
+            board.set(point, stone).map_err(|source| Set {
+                index,
+                point,
+                source,
+            })?;
+        }
+        count += 1;
+    }
+    Ok(count)
+}
+
+fn read_record<R: BufRead>(
+    reader: &mut LineReader<R>,
+    options: &LoadOptions,
+) -> Result<Board, LoadRecordError> {
+    let (header, lines) = read_header_lines(reader)?;
+    let mut board = Board::new(header.bounds);
+    board.set_win_rule(header.rule);
+    let count = read_moves(reader, TAIL_LINE, options, &mut board)?;
+    finish_moves(&mut board, &header, &lines, count, options)?;
+    Ok(board)
+}
+
+// Parses a move written by `Board::save_record_lines`, like `Black J10`,
+// `White (20, -3) 1500ms` or `Black pass`.
+fn parse_move_line(line: &str) -> Option<(Point, Stone, Option<Duration>)> {
+    let (stone, mut rest) = line.split_once(' ')?;
+    let stone = stone.parse().ok()?;
+    let mut time = None;
+    if let Some((point, millis)) = rest.rsplit_once(' ') {
+        if let Some(millis) = millis.strip_suffix("ms") {
+            time = Some(Duration::from_millis(millis.parse().ok()?));
+            rest = point;
+        }
+    }
+    let point = match rest {
+        "pass" => Point::PASS,
+        _ => Point::from_algebraic(rest).or_else(|| rest.parse().ok())?,
+    };
+    Some((point, stone, time))
+}
+
+// Checks the count of moves read, and applies the turn and the result.
+fn finish_moves(
+    board: &mut Board,
+    header: &RecordHeader,
+    lines: &HeaderLines,
+    count: usize,
+    options: &LoadOptions,
+) -> Result<(), LoadRecordError> {
+    if let (Some(expected), Some(line)) = (header.count, lines.count) {
+        if options.enforce_count && expected != count {
+            return Err(LoadRecordError::Data {
+                line,
+                msg: "wrong count",
+            });
+        }
+    }
+    if let Some(line) = lines.times {
+        if header.times.len() != count {
+            return Err(LoadRecordError::Data {
+                line,
+                msg: "wrong number of times",
+            });
+        }
+        board.times[board.index - count..board.index].copy_from_slice(&header.times);
+    }
+    if let Some(line) = lines.opening {
+        if !board.mark_swapped() {
+            return Err(LoadRecordError::Data {
+                line,
+                msg: "swapped opening stone is not white",
+            });
+        }
+    }
+    if let Some(turn) = header.turn {
+        board.set_turn(turn).expect("parsed turns should be valid");
+    }
+    if let Some(stone) = header.resigned {
+        board.resign(stone);
+    }
+    Ok(())
+}
+
+/// A summary of a record checked by [`Board::verify_record`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecordSummary {
+    pub bounds: Bounds,
+    /// The number of moves in the record, passes included.
+    pub count: usize,
+    /// The outcome of the game at the end of the record, if it is over.
+    pub outcome: Option<Outcome>,
+}
+
+impl Board {
+    // Returns the oldest format version that holds the headers of the board.
+    fn format_version(&self) -> u32 {
+        let timed = self.times[..self.index].iter().any(Option::is_some);
+        if self.rule != WinRule::SixOrMore || timed || self.is_swapped() {
+            2
+        } else {
+            1
+        }
+    }
 
+    pub fn save_record<W: Write>(&self, writer: W) -> io::Result<()> {
+        self.save_record_with(writer, &SaveOptions::default())
+    }
+
+    pub fn save_record_with<W: Write>(
+        &self,
+        mut writer: W,
+        options: &SaveOptions,
+    ) -> io::Result<()> {
+        writeln!(writer, "{HEADER_LINE}")?;
+        writeln!(writer, "Format: {}", self.format_version())?;
+        if options.version {
+            writeln!(writer, "{VERSION_LINE}")?;
+        }
+        writeln!(writer, "Board: {}", format_bounds(self.bounds))?;
+        writeln!(writer, "Count: {}", self.index())?;
+        match self.rule {
+            WinRule::SixOrMore => {}
+            WinRule::ExactlySix => writeln!(writer, "Rule: ExactlySix")?,
+        }
+        if self.is_swapped() {
+            writeln!(writer, "Opening: Swapped")?;
+        }
+        if let Some(Turn { stone, remaining }) = self.turn {
+            writeln!(writer, "Turn: {stone}({remaining})")?;
+        }
+        if let Some(stone) = self.resigned {
+            writeln!(writer, "Result: Resign({stone})")?;
+        }
+        if let Some(times) = format_times(&self.times[..self.index]) {
+            writeln!(writer, "Times: {times}")?;
+        }
+        writeln!(writer)?;
+
+        write_moves(&mut writer, self.past_record(), options)?;
         writeln!(writer, "{TAIL_LINE}")
     }
 
-    pub fn load_record<R: BufRead>(reader: R) -> Result<Board, LoadRecordError> {
+    /// Same as [`Board::save_record`], but returns the record as a string.
+    pub fn to_string_record(&self) -> String {
+        let mut record = Vec::new();
+        self.save_record(&mut record)
+            .expect("writing to a Vec should not fail");
+        String::from_utf8(record).expect("records should be ASCII")
+    }
+
+    /// Writes the moves after the first `from_index` ones in the past record
+    /// as a segment, to be appended to a record of those first moves and
+    /// read with [`Board::load_segmented_record`].
+    ///
+    /// Each segment has its own count and checksum, so that a game can be
+    /// logged incrementally by appending segments to a file.
+    pub fn append_record<W: Write>(&self, writer: W, from_index: usize) -> io::Result<()> {
+        self.append_record_with(writer, from_index, &SaveOptions::default())
+    }
+
+    /// Same as [`Board::append_record`], but with the given options.
+    pub fn append_record_with<W: Write>(
+        &self,
+        mut writer: W,
+        from_index: usize,
+        options: &SaveOptions,
+    ) -> io::Result<()> {
+        let from_index = from_index.min(self.index);
+        writeln!(writer, "{SEGMENT_HEADER_LINE}")?;
+        writeln!(writer, "Format: 2")?;
+        if options.version {
+            writeln!(writer, "{VERSION_LINE}")?;
+        }
+        writeln!(writer, "From: {from_index}")?;
+        writeln!(writer, "Count: {}", self.index - from_index)?;
+        if from_index == 0 && self.is_swapped() {
+            writeln!(writer, "Opening: Swapped")?;
+        }
+        if let Some(Turn { stone, remaining }) = self.turn {
+            writeln!(writer, "Turn: {stone}({remaining})")?;
+        }
+        if let Some(stone) = self.resigned {
+            writeln!(writer, "Result: Resign({stone})")?;
+        }
+        if let Some(times) = format_times(&self.times[from_index..self.index]) {
+            writeln!(writer, "Times: {times}")?;
+        }
+        writeln!(writer)?;
+
+        let moves = self.moves_since(from_index);
+        write_moves(&mut writer, moves, options)?;
+        writeln!(writer, "{SEGMENT_TAIL_LINE}")
+    }
+
+    /// Writes the past record with one move per line, like `Black J10`, for
+    /// keeping records under version control: making a move then adds a
+    /// single line, while the base64 body of [`Board::save_record`] changes
+    /// as a whole.
+    ///
+    /// Points are in algebraic notation where possible, or else `(x, y)`,
+    /// and passes are written as `pass`. The time taken by a move, if known,
+    /// follows in milliseconds, like `White K10 1500ms`. The headers are
+    /// those of a record, without `Count` and `Times`.
+    pub fn save_record_lines<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "{LINES_HEADER_LINE}")?;
+        writeln!(writer, "Format: {}", self.format_version())?;
+        writeln!(writer, "Board: {}", format_bounds(self.bounds))?;
+        match self.rule {
+            WinRule::SixOrMore => {}
+            WinRule::ExactlySix => writeln!(writer, "Rule: ExactlySix")?,
+        }
+        if self.is_swapped() {
+            writeln!(writer, "Opening: Swapped")?;
+        }
+        if let Some(Turn { stone, remaining }) = self.turn {
+            writeln!(writer, "Turn: {stone}({remaining})")?;
+        }
+        if let Some(stone) = self.resigned {
+            writeln!(writer, "Result: Resign({stone})")?;
+        }
+        writeln!(writer)?;
+
+        for (&(point, stone), time) in self.past_record().iter().zip(&self.times) {
+            if point == Point::PASS {
+                write!(writer, "{stone} pass")?;
+            } else {
+                let point = point.to_algebraic().unwrap_or_else(|| point.to_string());
+                write!(writer, "{stone} {point}")?;
+            }
+            if let Some(time) = time {
+                write!(writer, " {}ms", time.as_millis())?;
+            }
+            writeln!(writer)?;
+        }
+        writeln!(writer, "{LINES_TAIL_LINE}")
+    }
+
+    /// Reads a record written by [`Board::save_record_lines`].
+    ///
+    /// Blank lines between the moves are skipped.
+    pub fn load_record_lines<R: BufRead>(reader: R) -> Result<Board, LoadRecordError> {
         use LoadRecordError::*;
 
         let mut reader = LineReader::new(reader);
-
-        if reader.read_line()? != Some(HEADER_LINE) {
-            return Err(Syntax("expected header line"));
+        if reader.read_line()?.map(str::trim_end) != Some(LINES_HEADER_LINE) {
+            return Err(Syntax {
+                line: reader.line,
+                msg: "expected header line",
+            });
         }
+        let (header, lines) = read_header_fields(&mut reader)?;
+        let mut board = Board::new(header.bounds);
+        board.set_win_rule(header.rule);
 
-        let mut bounds = Bounds::Infinite;
-        let mut count = None;
+        let mut count = 0;
         loop {
-            let line = reader.read_line()?.ok_or(Syntax("unexpected EOF"))?;
-            let line = line.trim_end();
+            let line_no = reader.line + 1;
+            let line = reader.read_line()?.ok_or(Syntax {
+                line: line_no,
+                msg: "unexpected EOF",
+            })?;
+            let line = line.trim();
             if line.is_empty() {
+                continue;
+            }
+            if line == LINES_TAIL_LINE {
                 break;
             }
+            let (point, stone, time) = parse_move_line(line).ok_or(Syntax {
+                line: line_no,
+                msg: "invalid move",
+            })?;
+
+            let index = board.index();
+            if point == Point::PASS {
+                board.push_pass(stone);
+            } else {
+                board.set(point, stone).map_err(|source| Set {
+                    index,
+                    point,
+                    source,
+                })?;
+            }
+            board.times[index] = time;
+            count += 1;
+        }
+        finish_moves(&mut board, &header, &lines, count, &LoadOptions::default())?;
+        Ok(board)
+    }
+
+    /// Reads a record written by [`Board::save_record`], stopping at its
+    /// tail line.
+    ///
+    /// Fails if segments written by [`Board::append_record`] follow, which
+    /// are to be read with [`Board::load_segmented_record`]. Anything else
+    /// after the blank lines following the tail line is ignored.
+    pub fn load_record<R: BufRead>(reader: R) -> Result<Board, LoadRecordError> {
+        Board::load_record_lenient(reader, &LoadOptions::default())
+    }
+
+    /// Reads all the records concatenated in a file, such as an archive of
+    /// games, in order.
+    ///
+    /// The records may be separated by blank lines. An empty file holds no
+    /// records.
+    pub fn load_records<R: BufRead>(reader: R) -> Result<Vec<Board>, LoadRecordError> {
+        let options = LoadOptions::default();
+        let mut reader = LineReader::new(reader);
+        let mut boards = Vec::new();
+        while let Some(line) = reader.read_line()? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            reader.unread();
+            boards.push(read_record(&mut reader, &options)?);
+        }
+        Ok(boards)
+    }
+
+    /// Same as [`Board::load_record`], but reads from a string.
+    pub fn from_str_record(s: &str) -> Result<Board, LoadRecordError> {
+        Board::load_record(s.as_bytes())
+    }
 
-            let (key, value) = line
-                .split_once(':')
-                .ok_or(Syntax("expected colon in header"))?;
-            let value = value.trim_start();
-            match key {
-                "Board" => {
-                    bounds = parse_bounds(value).ok_or(Syntax("invalid header: Board"))?;
+    /// Same as [`Board::load_record`], but with some checks optionally
+    /// skipped, for recovering damaged records.
+    pub fn load_record_lenient<R: BufRead>(
+        reader: R,
+        options: &LoadOptions,
+    ) -> Result<Board, LoadRecordError> {
+        let mut reader = LineReader::new(reader);
+        let board = read_record(&mut reader, options)?;
+        while let Some(line) = reader.read_line()? {
+            match line.trim() {
+                "" => continue,
+                SEGMENT_HEADER_LINE => {
+                    return Err(LoadRecordError::Syntax {
+                        line: reader.line,
+                        msg: "unexpected segment",
+                    })
                 }
-                "Count" => match value.parse::<usize>() {
-                    Ok(res) => count = Some(res),
-                    Err(_) => return Err(Syntax("invalid header: Count")),
-                },
-                _ => {}
+                _ => break,
             }
         }
+        Ok(board)
+    }
+
+    /// Same as [`Board::load_record`], but also reads the segments written
+    /// by [`Board::append_record`] after the record, in order.
+    ///
+    /// A segment may start before the end of the moves read so far, in which
+    /// case the moves after its start are replaced.
+    pub fn load_segmented_record<R: BufRead>(reader: R) -> Result<Board, LoadRecordError> {
+        use LoadRecordError::*;
+
+        let options = LoadOptions::default();
+        let mut reader = LineReader::new(reader);
+        let mut board = read_record(&mut reader, &options)?;
 
-        let mut rec_buf = Vec::new();
-        let mut line;
         loop {
-            line = reader.read_line()?.ok_or(Syntax("unexpected EOF"))?;
-            if line.starts_with('=') {
+            let line_no = reader.line + 1;
+            let Some(line) = reader.read_line()? else {
                 break;
+            };
+            match line.trim() {
+                "" => continue,
+                SEGMENT_HEADER_LINE => {}
+                _ => {
+                    return Err(Syntax {
+                        line: line_no,
+                        msg: "expected segment header line",
+                    })
+                }
             }
-            BASE64_STANDARD.decode_vec(line, &mut rec_buf)?;
-        }
 
-        if !(line.starts_with('=') && line.len() == 5) {
-            return Err(Syntax("expected checksum"));
+            let (header, lines) = read_header_fields(&mut reader)?;
+            let (from, from_line) = lines.from.ok_or(Syntax {
+                line: line_no,
+                msg: "missing header: From",
+            })?;
+            // The rule is that of the record, for all of its segments.
+            if let Some(line) = lines.rule.filter(|_| header.rule != board.rule) {
+                return Err(Data {
+                    line,
+                    msg: "segment changes the rule",
+                });
+            }
+            if from != board.index() && board.jump(from).is_err() {
+                return Err(Data {
+                    line: from_line,
+                    msg: "segment starts past the end",
+                });
+            }
+            let count = read_moves(&mut reader, SEGMENT_TAIL_LINE, &options, &mut board)?;
+            finish_moves(&mut board, &header, &lines, count, &options)?;
         }
+        Ok(board)
+    }
 
-        let mut crc = [0; 4];
-        match BASE64_STANDARD.decode_slice(&line.as_bytes()[1..5], &mut crc) {
-            Ok(_) => (),
-            Err(DecodeSliceError::DecodeError(e)) => return Err(LoadRecordError::Base64(e)),
-            Err(DecodeSliceError::OutputSliceTooSmall) => unreachable!(),
-        }
+    /// Reads the headers of a record, stopping at the blank line after them
+    /// without reading the moves.
+    pub fn read_header<R: BufRead>(reader: R) -> Result<RecordHeader, LoadRecordError> {
+        read_header_lines(&mut LineReader::new(reader)).map(|(header, _)| header)
+    }
 
-        if u32::from_le_bytes(crc) != crc24(&rec_buf) {
-            return Err(Data("wrong checksum"));
+    /// Checks that a record is well-formed and that all of its moves are
+    /// legal, without keeping the board around.
+    pub fn verify_record<R: BufRead>(reader: R) -> Result<RecordSummary, LoadRecordError> {
+        let board = Board::load_record(reader)?;
+        Ok(RecordSummary {
+            bounds: board.bounds(),
+            count: board.total_count(),
+            outcome: board.outcome(),
+        })
+    }
+
+    /// Encodes the position in one line, like `Rect(19*19):White(2):AQ`,
+    /// with the bounds, the turn and the stones on the board.
+    ///
+    /// Unlike a record, the move history is discarded: the stones are
+    /// stored by index and delta-encoded, so that sparse positions stay short.
+    /// The base64 is URL-safe and unpadded.
+    pub fn to_position_string(&self) -> String {
+        let mut stones: Vec<_> = self.stones().map(|(p, s)| (p.index(), s)).collect();
+        stones.sort_unstable_by_key(|&(i, _)| i);
+
+        let mut buf = Vec::new();
+        let mut next = 0;
+        for (i, stone) in stones {
+            write_var_u65(&mut buf, i - next, stone as u8);
+            next = i + 1;
         }
 
-        let mut board = Board::new(bounds);
-        let mut rec_buf = &rec_buf[..];
-        let mut actual_count = 0;
-        while !rec_buf.is_empty() {
-            let Some((point_i, stone_i)) = read_var_u65(&mut rec_buf) else {
-                return Err(Data("malformed varint"));
-            };
+        let bounds = format_bounds(self.bounds);
+        let Turn { stone, remaining } = self.turn();
+        let data = BASE64_URL_SAFE_NO_PAD.encode(buf);
+        format!("{bounds}:{stone}({remaining}):{data}")
+    }
+
+    /// Decodes a position encoded by [`Board::to_position_string`].
+    ///
+    /// The stones are placed in the order of their indexes, with the turn
+    /// set explicitly.
+    pub fn from_position_string(s: &str) -> Result<Board, ParsePositionError> {
+        let mut parts = s.trim().split(':');
+        let (Some(bounds), Some(turn), Some(data), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(ParsePositionError);
+        };
+        let bounds = parse_bounds(bounds).ok_or(ParsePositionError)?;
+        let turn = parse_turn(turn).ok_or(ParsePositionError)?;
+        let buf = BASE64_URL_SAFE_NO_PAD
+            .decode(data)
+            .map_err(|_| ParsePositionError)?;
 
-            let point = Point::from_index(point_i);
+        let mut board = Board::new(bounds);
+        let mut rest = &buf[..];
+        let mut next = 0u64;
+        while !rest.is_empty() {
+            let (delta, stone_i) = read_var_u65(&mut rest).ok_or(ParsePositionError)?;
+            let i = next.checked_add(delta).ok_or(ParsePositionError)?;
             let stone = match stone_i {
                 0 => Stone::Black,
                 _ => Stone::White,
             };
-
-            board.set(point, stone)?;
-            actual_count += 1;
-        }
-
-        if let Some(count) = count {
-            if count != actual_count {
-                return Err(Data("wrong count"));
-            }
+            // Passes are rejected as out of bounds.
+            board
+                .set(Point::from_index(i), stone)
+                .map_err(|_| ParsePositionError)?;
+            next = i + 1;
         }
+        board.set_turn(turn).expect("parsed turns should be valid");
         Ok(board)
     }
 }
+
+#[cfg(feature = "flate2")]
+impl Board {
+    /// Same as [`Board::save_record`], but compresses the record with gzip.
+    pub fn save_record_gz<W: Write>(&self, writer: W) -> io::Result<()> {
+        let mut encoder = flate2::write::GzEncoder::new(writer, flate2::Compression::default());
+        self.save_record(&mut encoder)?;
+        encoder.finish()?;
+        Ok(())
+    }
+
+    /// Same as [`Board::load_record`], but for a record compressed with gzip.
+    pub fn load_record_gz<R: Read>(reader: R) -> Result<Board, LoadRecordError> {
+        Board::load_record(io::BufReader::new(flate2::read::GzDecoder::new(reader)))
+    }
+}
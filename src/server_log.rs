@@ -0,0 +1,123 @@
+use std::io::BufRead;
+
+use crate::{Board, LoadRecordError, OpeningError, Point, SetError, Stone};
+
+/// Options for [`Board::from_server_log_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServerLogOptions {
+    /// The distance from the top-left corner to the center of the board,
+    /// which is a square of `offset * 2 + 1` points. Columns are single
+    /// letters, so only the first 26 can be named, up to an offset of 12.
+    pub offset: u8,
+    /// Whether to place the opening black stone at the center when the log
+    /// leaves it out, i.e. unless its first turn is a single stone.
+    pub auto_first: bool,
+}
+
+impl Default for ServerLogOptions {
+    fn default() -> ServerLogOptions {
+        ServerLogOptions {
+            offset: 9,
+            auto_first: true,
+        }
+    }
+}
+
+// Parses a point like `K10`, counted from `A1` at the top-left corner.
+fn parse_point(s: &str, offset: i32) -> Option<Point> {
+    if s.is_empty() || !s.is_char_boundary(1) {
+        return None;
+    }
+    let (col, row) = s.split_at(1);
+    let col = col.as_bytes()[0];
+    if !col.is_ascii_uppercase() {
+        return None;
+    }
+    let x = (col - b'A') as i32 - offset;
+    let y = row.parse::<i32>().ok()?.checked_sub(1 + offset)?;
+    Some(Point::new(x, y))
+}
+
+impl Board {
+    /// Reads the moves from a game server log, where each turn is a line
+    /// like `Black moved (K10, K11)`, or `Black moved (J10)` for an opening
+    /// move. Anything before the player, such as a timestamp, is ignored.
+    /// Lines without `moved` are skipped.
+    ///
+    /// This uses the default [`ServerLogOptions`], for a 19 by 19 board with
+    /// the opening move placed when left out.
+    pub fn from_server_log<R: BufRead>(reader: R) -> Result<Board, LoadRecordError> {
+        Board::from_server_log_with(reader, &ServerLogOptions::default())
+    }
+
+    /// Same as [`Board::from_server_log`], but with the given options.
+    pub fn from_server_log_with<R: BufRead>(
+        reader: R,
+        options: &ServerLogOptions,
+    ) -> Result<Board, LoadRecordError> {
+        use LoadRecordError::*;
+
+        let offset = options.offset as i32;
+        let mut board = Board::new_square(options.offset as u32 * 2 + 1);
+        // The line of each move, for reporting errors in the turns.
+        let mut move_lines = Vec::new();
+
+        for (i, line) in reader.lines().enumerate() {
+            let line_no = i + 1;
+            let line = line?;
+            if !line.contains("moved") {
+                continue;
+            }
+            // The player is the word just before `moved`.
+            let stone: Stone = line
+                .split_once("moved")
+                .and_then(|(before, _)| before.split_whitespace().next_back())
+                .and_then(|word| word.parse().ok())
+                .ok_or(Syntax {
+                    line: line_no,
+                    msg: "unknown player",
+                })?;
+            let points = line
+                .split_once('(')
+                .and_then(|(_, s)| s.trim_end().strip_suffix(')'))
+                .and_then(|s| {
+                    s.split(", ")
+                        .map(|p| parse_point(p, offset))
+                        .collect::<Option<Vec<_>>>()
+                })
+                .filter(|points| (1..=2).contains(&points.len()))
+                .ok_or(Syntax {
+                    line: line_no,
+                    msg: "malformed coordinates",
+                })?;
+
+            let mut moves = Vec::with_capacity(3);
+            if move_lines.is_empty() && options.auto_first && points.len() == 2 {
+                moves.push((Point::ORIGIN, Stone::Black));
+            }
+            moves.extend(points.into_iter().map(|point| (point, stone)));
+            for (point, stone) in moves {
+                board.set(point, stone).map_err(|err| Data {
+                    line: line_no,
+                    msg: match err {
+                        SetError::Occupied => "point is occupied",
+                        SetError::OutOfBounds => "point is out of bounds",
+                    },
+                })?;
+                move_lines.push(line_no);
+            }
+        }
+
+        board.validate_opening().map_err(|err| match err {
+            OpeningError::TurnTooLong { index, .. } => Data {
+                line: move_lines[index],
+                msg: "turn is too long",
+            },
+            OpeningError::TurnTooShort { index, .. } => Data {
+                line: move_lines[index],
+                msg: "turn is too short",
+            },
+        })?;
+        Ok(board)
+    }
+}
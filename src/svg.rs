@@ -0,0 +1,106 @@
+use std::fmt::Write;
+
+use crate::{is_star_point, Board, Point, Stone};
+
+const CELL_SIZE: i64 = 24;
+const MARGIN: i64 = 24;
+const STONE_RADIUS: i64 = 11;
+const STAR_POINT_RADIUS: i64 = 3;
+// The largest number of points per side of a window.
+const MAX_SIZE: u64 = 1024;
+
+/// An error from [`Board::render_svg`] for a window too large to render.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("window is {width} by {height} points, more than {MAX_SIZE} per side")]
+pub struct SvgSizeError {
+    pub width: u64,
+    pub height: u64,
+}
+
+impl Board {
+    /// Renders the stones within a window as an SVG image.
+    ///
+    /// The window is given by its top-left and bottom-right corners, both
+    /// inclusive, and defaults to the occupied bounds of the board.
+    ///
+    /// Fails if the window is more than 1024 points wide or high, as can be
+    /// the occupied bounds of stones far apart on an unbounded board.
+    pub fn render_svg(&self, window: Option<(Point, Point)>) -> Result<String, SvgSizeError> {
+        let (min, max) = window
+            .or_else(|| self.occupied_bounds())
+            .unwrap_or((Point::ORIGIN, Point::ORIGIN));
+        let cols = (max.x as i64 - min.x as i64).max(0);
+        let rows = (max.y as i64 - min.y as i64).max(0);
+        if cols as u64 >= MAX_SIZE || rows as u64 >= MAX_SIZE {
+            return Err(SvgSizeError {
+                width: cols as u64 + 1,
+                height: rows as u64 + 1,
+            });
+        }
+        let width = cols * CELL_SIZE + MARGIN * 2;
+        let height = rows * CELL_SIZE + MARGIN * 2;
+
+        // Mirrors the point-to-cell mapping of the TUI, with y going down.
+        let pos = |p: Point| {
+            (
+                MARGIN + (p.x as i64 - min.x as i64) * CELL_SIZE,
+                MARGIN + (p.y as i64 - min.y as i64) * CELL_SIZE,
+            )
+        };
+        let in_window = |p: Point| (min.x..=max.x).contains(&p.x) && (min.y..=max.y).contains(&p.y);
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+        );
+        let _ = writeln!(
+            svg,
+            r##"<rect width="100%" height="100%" fill="#dcb35c"/>"##
+        );
+
+        let (left, top) = (MARGIN, MARGIN);
+        let (right, bottom) = (width - MARGIN, height - MARGIN);
+        for i in 0..=cols {
+            let x = MARGIN + i * CELL_SIZE;
+            let _ = writeln!(
+                svg,
+                r#"<line x1="{x}" y1="{top}" x2="{x}" y2="{bottom}" stroke="black"/>"#
+            );
+        }
+        for i in 0..=rows {
+            let y = MARGIN + i * CELL_SIZE;
+            let _ = writeln!(
+                svg,
+                r#"<line x1="{left}" y1="{y}" x2="{right}" y2="{y}" stroke="black"/>"#
+            );
+        }
+
+        for (point, _) in self.region(min, (cols as u32 + 1, rows as u32 + 1), true) {
+            if in_window(point) && is_star_point(self.bounds, point) {
+                let (cx, cy) = pos(point);
+                let _ = writeln!(
+                    svg,
+                    r#"<circle cx="{cx}" cy="{cy}" r="{STAR_POINT_RADIUS}" fill="black"/>"#
+                );
+            }
+        }
+
+        for (point, stone) in self.stones() {
+            if in_window(point) {
+                let (cx, cy) = pos(point);
+                let fill = match stone {
+                    Stone::Black => "black",
+                    Stone::White => "white",
+                };
+                let _ = writeln!(
+                    svg,
+                    r#"<circle cx="{cx}" cy="{cy}" r="{STONE_RADIUS}" fill="{fill}" stroke="black"/>"#
+                );
+            }
+        }
+
+        svg.push_str("</svg>\n");
+        Ok(svg)
+    }
+}
@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+use crate::{sort_points, Board, Bounds, ByStone, Direction, Point, RawBoard, Stone};
+
+// The windows and neighborhoods of the stones on a board, updated as each
+// stone is placed or removed, so that evaluating the position takes no scan.
+//
+// Every update is undone by its reverse, since only counts are kept, so a
+// stone can be removed without looking at the rest of the board.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct ThreatMap {
+    // The number of stones of each color in every window of six points in
+    // a line, within bounds, that holds any.
+    windows: HashMap<(Point, Direction), ByStone<u8>>,
+    // The counts returned by `Board::count_patterns`.
+    patterns: ByStone<[u32; 6]>,
+    // The number of stones within a Chebyshev distance of 2 of every point
+    // within bounds that has any.
+    nearby: HashMap<Point, u32>,
+}
+
+// Returns the color a window counts towards in the patterns, along with the
+// number of its stones, if it holds stones of only one color.
+fn pattern(counts: ByStone<u8>) -> Option<(Stone, usize)> {
+    match (counts[Stone::Black], counts[Stone::White]) {
+        (0, 0) => None,
+        (n, 0) => Some((Stone::Black, n as usize)),
+        (0, n) => Some((Stone::White, n as usize)),
+        _ => None,
+    }
+}
+
+impl ThreatMap {
+    // Computes the map from scratch for the stones on a raw board.
+    fn from_board(board: &RawBoard, bounds: Bounds) -> ThreatMap {
+        let mut threats = ThreatMap::default();
+        for (block_i, black, white) in board.bitboards() {
+            for (stone, mut mask) in [(Stone::Black, black), (Stone::White, white)] {
+                while mask != 0 {
+                    let point = Point::from_index(block_i << 6 | mask.trailing_zeros() as u64);
+                    threats.update(bounds, point, stone, true);
+                    mask &= mask - 1;
+                }
+            }
+        }
+        threats
+    }
+
+    fn update(&mut self, bounds: Bounds, point: Point, stone: Stone, placed: bool) {
+        for dir in Direction::ALL {
+            let (dx, dy) = dir.delta();
+            for i in 0..6 {
+                let start = point - Point::new(dx * i, dy * i);
+                if !(0..6).all(|j| bounds.contains(start + Point::new(dx * j, dy * j))) {
+                    continue;
+                }
+                let counts = self.windows.entry((start, dir)).or_default();
+                if let Some((stone, n)) = pattern(*counts) {
+                    self.patterns[stone][n - 1] -= 1;
+                }
+                if placed {
+                    counts[stone] += 1;
+                } else {
+                    counts[stone] -= 1;
+                }
+                if let Some((stone, n)) = pattern(*counts) {
+                    self.patterns[stone][n - 1] += 1;
+                }
+                if *counts == ByStone::default() {
+                    self.windows.remove(&(start, dir));
+                }
+            }
+        }
+
+        for near in point.disk(2).filter(|&near| bounds.contains(near)) {
+            let count = self.nearby.entry(near).or_default();
+            if placed {
+                *count += 1;
+            } else {
+                *count -= 1;
+                if *count == 0 {
+                    self.nearby.remove(&near);
+                }
+            }
+        }
+    }
+}
+
+impl Board {
+    /// Starts keeping the [`Board::count_patterns`] and the
+    /// [`Board::candidate_moves`] up to date as stones are placed and
+    /// removed, so that they no longer scan the stones on each call.
+    ///
+    /// This makes every move slower in exchange, and is meant for engines
+    /// evaluating many positions. Cloning the board keeps the tracking on.
+    ///
+    /// In debug builds, the map is checked against one computed from
+    /// scratch after every update, which makes each move take time
+    /// proportional to the number of stones.
+    pub fn enable_threat_tracking(&mut self) {
+        self.threats = Some(Box::new(ThreatMap::from_board(&self.board, self.bounds)));
+    }
+
+    /// Stops the tracking started by [`Board::enable_threat_tracking`].
+    pub fn disable_threat_tracking(&mut self) {
+        self.threats = None;
+    }
+
+    pub fn is_tracking_threats(&self) -> bool {
+        self.threats.is_some()
+    }
+
+    // Updates the tracked threats, if any, for a stone just placed on or
+    // removed from the raw board.
+    pub(crate) fn track_stone(&mut self, point: Point, stone: Stone, placed: bool) {
+        if let Some(threats) = &mut self.threats {
+            threats.update(self.bounds, point, stone, placed);
+            debug_assert!(
+                **threats == ThreatMap::from_board(&self.board, self.bounds),
+                "tracked threats drifted after {stone} at {point} was {}",
+                if placed { "placed" } else { "removed" }
+            );
+        }
+    }
+
+    pub(crate) fn tracked_patterns(&self, stone: Stone) -> Option<[u32; 6]> {
+        self.threats.as_ref().map(|threats| threats.patterns[stone])
+    }
+
+    pub(crate) fn tracked_candidates(&self) -> Option<Vec<Point>> {
+        let threats = self.threats.as_ref()?;
+        let mut points: Vec<Point> = threats
+            .nearby
+            .keys()
+            .copied()
+            .filter(|&point| self.is_empty_at(point))
+            .collect();
+        sort_points(&mut points);
+        Some(points)
+    }
+}
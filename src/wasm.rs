@@ -0,0 +1,93 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{Board, Bounds, Point, Stone};
+
+/// A [`Board`] exported to JavaScript as `Board`, built with the `wasm` feature:
+///
+/// ```text
+/// cargo build --lib --release --target wasm32-unknown-unknown \
+///     --no-default-features --features wasm
+/// ```
+///
+/// The crate is then exported as a single `Board` class, where points are
+/// plain `x` and `y` numbers and stones are told apart by `isWhite`:
+///
+/// ```js
+/// const board = new Board(19, 19); // or `new Board()` for an infinite one
+/// board.place(0, 0, false);        // throws if the move is illegal
+/// board.place(1, 0, true);
+/// board.get(0, 0);                 // 0 if empty, 1 if black, 2 if white
+/// board.nextIsWhite();             // true
+/// board.occupiedBounds();          // [0, 0, 1, 0] as [minX, minY, maxX, maxY]
+/// const json = board.toJson();     // see `Board::to_json`
+/// Board.fromJson(json);            // throws if the JSON is invalid
+/// ```
+#[wasm_bindgen(js_name = Board)]
+pub struct WasmBoard {
+    board: Board,
+}
+
+#[wasm_bindgen(js_class = Board)]
+impl WasmBoard {
+    /// Creates a `width` by `height` board, or an infinite one if either
+    /// is missing.
+    #[wasm_bindgen(constructor)]
+    pub fn new(width: Option<u32>, height: Option<u32>) -> WasmBoard {
+        let bounds = match (width, height) {
+            (Some(width), Some(height)) => Bounds::Rect(width, height),
+            _ => Bounds::Infinite,
+        };
+        WasmBoard {
+            board: Board::new(bounds),
+        }
+    }
+
+    pub fn place(&mut self, x: i32, y: i32, is_white: bool) -> Result<(), JsError> {
+        let stone = if is_white { Stone::White } else { Stone::Black };
+        self.board.set(Point::new(x, y), stone)?;
+        Ok(())
+    }
+
+    pub fn pass(&mut self) {
+        self.board.pass();
+    }
+
+    /// Undoes the last move, returning `false` if there is none.
+    pub fn undo(&mut self) -> bool {
+        self.board.unset().is_some()
+    }
+
+    /// Returns 0 for an empty point, 1 for black and 2 for white.
+    pub fn get(&self, x: i32, y: i32) -> u8 {
+        match self.board.get(Point::new(x, y)) {
+            None => 0,
+            Some(Stone::Black) => 1,
+            Some(Stone::White) => 2,
+        }
+    }
+
+    #[wasm_bindgen(js_name = nextIsWhite)]
+    pub fn next_is_white(&self) -> bool {
+        self.board.next_to_play() == Stone::White
+    }
+
+    /// Returns `[minX, minY, maxX, maxY]`, or `undefined` if the board is
+    /// empty.
+    #[wasm_bindgen(js_name = occupiedBounds)]
+    pub fn occupied_bounds(&self) -> Option<Vec<i32>> {
+        let (min, max) = self.board.occupied_bounds()?;
+        Some(vec![min.x, min.y, max.x, max.y])
+    }
+
+    #[wasm_bindgen(js_name = toJson)]
+    pub fn to_json(&self) -> String {
+        self.board.to_json()
+    }
+
+    #[wasm_bindgen(js_name = fromJson)]
+    pub fn from_json(json: &str) -> Result<WasmBoard, JsError> {
+        Ok(WasmBoard {
+            board: Board::from_json(json)?,
+        })
+    }
+}
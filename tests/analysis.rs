@@ -0,0 +1,367 @@
+use std::error::Error;
+
+use c6::*;
+
+mod common;
+use common::Lcg;
+
+#[test]
+fn test_winning_spots() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_square(19);
+    for x in [-2, -1, 0, 1, 2] {
+        board.set(Point::new(x, 0), Stone::Black)?;
+    }
+    assert_eq!(
+        board.winning_spots(Stone::Black),
+        [Point::new(-3, 0), Point::new(3, 0)]
+    );
+    assert!(board.winning_spots(Stone::White).is_empty());
+
+    // A gap in the middle of a line is a winning spot too.
+    for y in [-3, -2, -1, 1, 2] {
+        board.set(Point::new(5, y), Stone::White)?;
+    }
+    assert_eq!(board.winning_spots(Stone::White), [Point::new(5, 0)]);
+
+    // Lines running off the board cannot be completed there.
+    for x in [5, 6, 7, 8, 9] {
+        board.set(Point::new(x, 9), Stone::Black)?;
+    }
+    assert_eq!(board.winning_spots(Stone::Black).len(), 3);
+    assert!(board
+        .winning_spots(Stone::Black)
+        .contains(&Point::new(4, 9)));
+    Ok(())
+}
+
+#[test]
+fn test_outcome() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_infinite();
+    for y in [0, 1, 2, 3, 4] {
+        board.set(Point::new(0, y), Stone::White)?;
+    }
+    assert_eq!(board.check_win(Point::ORIGIN), None);
+    assert_eq!(board.outcome(), None);
+
+    board.set(Point::new(0, -1), Stone::White)?;
+    board.set(Point::new(0, -2), Stone::White)?;
+    let line = [-2, -1, 0, 1, 2, 3].map(|y| Point::new(0, y));
+    assert_eq!(board.check_win(Point::new(0, 2)), Some(line));
+    assert_eq!(
+        board.outcome(),
+        Some(Outcome::Win {
+            stone: Stone::White,
+            line
+        })
+    );
+
+    let mut board = Board::new_square(1);
+    board.set(Point::ORIGIN, Stone::Black)?;
+    assert_eq!(board.outcome(), Some(Outcome::Draw));
+
+    // Moving through the history withdraws a resignation.
+    let mut board = Board::new_infinite();
+    board.set(Point::ORIGIN, Stone::Black)?;
+    board.resign(Stone::White);
+    board.unset();
+    assert_eq!(board.outcome(), None);
+    board.reset();
+    assert_eq!(board.outcome(), None);
+    board.resign(Stone::White);
+    board.jump(0)?;
+    assert_eq!(board.outcome(), None);
+    Ok(())
+}
+
+#[test]
+fn test_cached_outcome() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_infinite();
+    for x in 0..5 {
+        board.set(Point::new(x, 0), Stone::Black)?;
+    }
+    assert!(!board.is_terminal());
+
+    board.set(Point::new(5, 0), Stone::Black)?;
+    assert!(board.is_terminal());
+    assert_eq!(board.cached_outcome(), board.outcome());
+
+    // Undoing the winning stone undoes the win.
+    board.unset();
+    assert!(!board.is_terminal());
+    board.reset();
+    assert_eq!(board.cached_outcome(), board.outcome());
+    board.jump(3)?;
+    assert_eq!(board.cached_outcome(), None);
+    board.jump(6)?;
+    assert_eq!(board.cached_outcome(), board.outcome());
+
+    // The first win is kept as the line grows.
+    board.set(Point::new(-1, 0), Stone::Black)?;
+    let line = [0, 1, 2, 3, 4, 5].map(|x| Point::new(x, 0));
+    assert_eq!(
+        board.cached_outcome(),
+        Some(Outcome::Win {
+            stone: Stone::Black,
+            line
+        })
+    );
+
+    let snap = board.snapshot();
+    board.jump(5)?;
+    board.resign(Stone::White);
+    assert_eq!(board.cached_outcome(), Some(Outcome::Resign(Stone::White)));
+    board.jump(7)?;
+    board.restore(&snap)?;
+    assert!(board.is_terminal());
+    Ok(())
+}
+
+#[test]
+fn test_win_rule() -> Result<(), Box<dyn Error>> {
+    for rule in [WinRule::SixOrMore, WinRule::ExactlySix] {
+        let mut six = Board::new_infinite();
+        six.set_win_rule(rule);
+        for x in 0..6 {
+            six.set(Point::new(x, 0), Stone::Black)?;
+        }
+        assert!(six.check_win(Point::new(2, 0)).is_some());
+        assert!(six.outcome().is_some());
+
+        let mut seven = Board::new_infinite();
+        seven.set_win_rule(rule);
+        for x in [0, 1, 2, 4, 5, 6] {
+            seven.set(Point::new(x, 0), Stone::Black)?;
+        }
+        let spots = seven.winning_spots(Stone::Black);
+        seven.set(Point::new(3, 0), Stone::Black)?;
+
+        let overline_wins = rule == WinRule::SixOrMore;
+        assert_eq!(seven.check_win(Point::new(3, 0)).is_some(), overline_wins);
+        assert_eq!(seven.outcome().is_some(), overline_wins);
+        assert_eq!(seven.is_terminal(), overline_wins);
+        assert_eq!(spots.contains(&Point::new(3, 0)), overline_wins);
+    }
+
+    // Changing the rule re-checks the past moves, as they were made.
+    let mut board = Board::new_infinite();
+    for x in [0, 1, 2, 4, 5, 6, 3, 7] {
+        board.set(Point::new(x, 0), Stone::White)?;
+    }
+    assert!(board.is_terminal());
+    board.set_win_rule(WinRule::ExactlySix);
+    assert!(!board.is_terminal());
+    board.set_win_rule(WinRule::SixOrMore);
+    assert!(board.is_terminal());
+
+    // The game ends at an exact six, even if it is extended later.
+    let mut board = Board::new_infinite();
+    board.set_win_rule(WinRule::ExactlySix);
+    for x in 0..7 {
+        board.set(Point::new(x, 0), Stone::White)?;
+    }
+    assert!(board.is_terminal());
+    assert!(board.outcome().is_some());
+    assert_eq!(board.outcome(), board.cached_outcome());
+    Ok(())
+}
+
+#[test]
+fn test_validate_opening() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_infinite();
+    board.validate_opening()?;
+    board.set(Point::ORIGIN, Stone::Black)?;
+    board.set(Point::new(1, 0), Stone::White)?;
+    board.set(Point::new(2, 0), Stone::White)?;
+    board.pass();
+    board.set(Point::new(3, 0), Stone::White)?;
+    board.validate_opening()?;
+
+    // Two black stones in the first turn.
+    let board = Board::from_moves(
+        Bounds::Infinite,
+        &[
+            (Point::ORIGIN, Stone::Black),
+            (Point::new(1, 0), Stone::Black),
+        ],
+    )
+    .unwrap();
+    assert_eq!(
+        board.validate_opening(),
+        Err(OpeningError::TurnTooLong {
+            index: 1,
+            stone: Stone::Black
+        })
+    );
+
+    // A single white stone in the second turn.
+    let board = Board::from_moves(
+        Bounds::Infinite,
+        &[
+            (Point::ORIGIN, Stone::Black),
+            (Point::new(1, 0), Stone::White),
+            (Point::new(2, 0), Stone::Black),
+        ],
+    )
+    .unwrap();
+    assert_eq!(
+        board.validate_opening(),
+        Err(OpeningError::TurnTooShort {
+            index: 2,
+            stone: Stone::White
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_longest_line() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_infinite();
+    assert_eq!(board.longest_line(Stone::Black), (0, None));
+
+    for (x, y) in [
+        (0, 0),
+        (1, 0),
+        (2, 0),
+        (5, 5),
+        (4, 4),
+        (3, 3),
+        (2, 2),
+        (9, 9),
+    ] {
+        board.set(Point::new(x, y), Stone::Black)?;
+    }
+    board.set(Point::new(3, 0), Stone::White)?;
+    assert_eq!(
+        board.longest_line(Stone::Black),
+        (4, Some([Point::new(2, 2), Point::new(5, 5)]))
+    );
+    assert_eq!(
+        board.longest_line(Stone::White),
+        (1, Some([Point::new(3, 0), Point::new(3, 0)]))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_score() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_infinite();
+    for x in 0..3 {
+        board.set(Point::new(x, 0), Stone::Black)?;
+    }
+    // Windows from x = -5 to 2 hold 1, 2, 3, 3, 3, 3, 2 and 1 stones,
+    // and each stone lies in 6 more windows in each other direction.
+    assert_eq!(board.count_patterns(Stone::Black), [2 + 54, 2, 4, 0, 0, 0]);
+    let weights = ScoreWeights::default();
+    let three = board.score(Stone::Black, &weights);
+    assert_eq!(board.score(Stone::White, &weights), -three);
+
+    board.set(Point::new(3, 0), Stone::Black)?;
+    let four = board.score(Stone::Black, &weights);
+    assert!(four > three);
+
+    // Blocking one end leaves fewer windows.
+    board.set(Point::new(4, 0), Stone::White)?;
+    assert_eq!(board.count_patterns(Stone::Black)[3], 1);
+    assert!(board.score(Stone::Black, &weights) < four);
+
+    let flat = ScoreWeights { by_count: [1; 6] };
+    assert_eq!(board.score(Stone::Black, &flat), (1 + 72 + 3) - (1 + 18));
+    Ok(())
+}
+
+#[test]
+fn test_density_map() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_infinite();
+    for (x, y) in [(0, 0), (1, 2), (3, 3), (-1, 0), (-4, -4), (9, -1)] {
+        board.set(Point::new(x, y), Stone::Black)?;
+    }
+    board.set(Point::new(2, 1), Stone::White)?;
+
+    let map = board.density_map(4);
+    let expected = [((0, 0), 4), ((-1, 0), 1), ((-1, -1), 1), ((2, -1), 1)];
+    assert_eq!(map, expected.into_iter().collect());
+    assert_eq!(map.values().sum::<u32>(), 7);
+
+    assert_eq!(board.density_map(1).len(), 7);
+    // Every quadrant is a single square.
+    let map = board.density_map(u32::MAX);
+    let expected = [((0, 0), 4), ((-1, 0), 1), ((-1, -1), 1), ((0, -1), 1)];
+    assert_eq!(map, expected.into_iter().collect());
+    Ok(())
+}
+
+#[test]
+fn test_threat_tracking() -> Result<(), Box<dyn Error>> {
+    fn assert_tracked(board: &Board) {
+        let mut full = board.clone();
+        full.disable_threat_tracking();
+        for stone in [Stone::Black, Stone::White] {
+            assert_eq!(board.count_patterns(stone), full.count_patterns(stone));
+        }
+        assert_eq!(board.candidate_moves(), full.candidate_moves());
+    }
+
+    for bounds in [Bounds::Rect(9, 7), Bounds::Infinite] {
+        let mut board = Board::new(bounds);
+        board.set(Point::ORIGIN, Stone::Black)?;
+        board.enable_threat_tracking();
+        assert!(board.is_tracking_threats());
+        assert!(board.swap_sides());
+        assert_tracked(&board);
+
+        // A fixed pseudo-random game, near the edges on the small board.
+        let mut rng = Lcg::new(12345);
+        for _ in 0..40 {
+            let (x, y) = (rng.below(9) as i32 - 4, rng.below(7) as i32 - 3);
+            if board.set(Point::new(x, y), board.turn().stone).is_ok() {
+                assert_tracked(&board);
+            }
+        }
+
+        // Removing stones is tracked as well.
+        while board.unset().is_some() {
+            assert_tracked(&board);
+        }
+        board.jump(board.total_count())?;
+        assert_tracked(&board);
+        board.jump(board.total_count() / 2)?;
+        assert_tracked(&board);
+
+        board.invert_colors();
+        assert_tracked(&board);
+        let transformed = board.transform(Symmetry::Rotate90);
+        assert!(transformed.is_tracking_threats());
+        assert_tracked(&transformed);
+    }
+    Ok(())
+}
+
+// Debug builds check the tracked threats against a full recompute on every
+// update, so this panics as soon as they drift.
+#[test]
+fn test_threat_tracking_verified() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new(Bounds::Rect(15, 15));
+    board.enable_threat_tracking();
+    let mut rng = Lcg::new(42);
+    let mut next = |n: u32| rng.below(n);
+    for _ in 0..500 {
+        match next(8) {
+            0 => {
+                board.unset();
+            }
+            1 => {
+                board.unset_turn();
+            }
+            2 => {
+                board.jump(next(board.total_count() as u32 + 1) as usize)?;
+            }
+            _ => {
+                let point = Point::new(next(15) as i32 - 7, next(15) as i32 - 7);
+                let _ = board.set(point, board.turn().stone);
+            }
+        }
+    }
+    assert!(board.is_tracking_threats());
+    assert!(board.total_count() > 0);
+    Ok(())
+}
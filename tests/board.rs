@@ -0,0 +1,1232 @@
+use std::{collections::HashSet, error::Error};
+
+use c6::*;
+
+#[test]
+fn test_count_by_color() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_infinite();
+    assert_eq!(board.count_by_color(), (0, 0));
+
+    board.set(Point::new(0, 0), Stone::Black)?;
+    board.set(Point::new(1, 0), Stone::White)?;
+    board.set(Point::new(-1, 5), Stone::White)?;
+    board.set(Point::new(100, -100), Stone::Black)?;
+    board.set(Point::new(-7, 31), Stone::Black)?;
+    assert_eq!(board.count_by_color(), (3, 2));
+
+    board.unset();
+    board.unset();
+    assert_eq!(board.count_by_color(), (1, 2));
+
+    board.jump(board.total_count())?;
+    assert_eq!(board.count_by_color(), (3, 2));
+    Ok(())
+}
+
+#[test]
+fn test_jump() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_infinite();
+    board.set(Point::new(0, 0), Stone::Black)?;
+    board.set(Point::new(1, 0), Stone::White)?;
+    board.set(Point::new(2, 0), Stone::White)?;
+
+    board.jump(1)?;
+    assert_eq!(board.index(), 1);
+    assert_eq!(board.get(Point::new(1, 0)), None);
+
+    board.jump(3)?;
+    assert_eq!(board.index(), 3);
+    assert_eq!(board.get(Point::new(2, 0)), Some(Stone::White));
+
+    let err = board.jump(4).unwrap_err();
+    assert_eq!((err.index, err.len), (4, 3));
+    assert_eq!(board.index(), 3);
+    Ok(())
+}
+
+#[test]
+fn test_rewind_fast_forward() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_infinite();
+    board.rewind();
+    board.fast_forward();
+    assert_eq!(board.index(), 0);
+
+    for x in 0..5 {
+        board.set(Point::new(x, 0), board.turn().stone)?;
+    }
+    board.jump(2)?;
+    board.rewind();
+    assert_eq!(board.index(), 0);
+    assert_eq!(board.get(Point::ORIGIN), None);
+    board.fast_forward();
+    assert_eq!(board.index(), 5);
+    assert_eq!(board.get(Point::new(4, 0)), Some(Stone::Black));
+
+    let mut board = Board::with_undo_limit(Bounds::Infinite, 2);
+    for x in 0..5 {
+        board.set(Point::new(x, 0), board.turn().stone)?;
+    }
+    board.rewind();
+    assert_eq!(board.index(), 3);
+    Ok(())
+}
+
+#[test]
+fn test_turn() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_infinite();
+    let turn = |stone, remaining| Turn { stone, remaining };
+    assert_eq!(board.turn(), turn(Stone::Black, 1));
+
+    board.set(Point::ORIGIN, Stone::Black)?;
+    assert_eq!(board.turn(), turn(Stone::White, 2));
+
+    board.set_turn(turn(Stone::Black, 2))?;
+    board.set(Point::new(1, 0), Stone::Black)?;
+    assert_eq!(board.turn(), turn(Stone::Black, 1));
+    board.set(Point::new(2, 0), Stone::Black)?;
+    assert_eq!(board.turn(), turn(Stone::White, 2));
+
+    board.unset();
+    assert_eq!(board.turn(), turn(Stone::White, 2));
+
+    let err = board.set_turn(turn(Stone::White, 0)).unwrap_err();
+    assert_eq!(err, SetTurnError { remaining: 0 });
+    assert!(board.set_turn(turn(Stone::White, 3)).is_err());
+    assert_eq!(board.turn(), turn(Stone::White, 2));
+    Ok(())
+}
+
+#[test]
+fn test_pass() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_infinite();
+    let turn = |stone, remaining| Turn { stone, remaining };
+
+    board.pass();
+    assert_eq!(board.turn(), turn(Stone::White, 2));
+    assert_eq!(board.total_count(), 1);
+
+    board.set(Point::ORIGIN, Stone::White)?;
+    assert_eq!(board.turn(), turn(Stone::White, 1));
+    board.pass();
+    assert_eq!(board.turn(), turn(Stone::Black, 2));
+    assert_eq!(board.stones().count(), 1);
+    assert_eq!(board.move_index_of(Point::PASS), None);
+
+    board.unset();
+    assert_eq!(board.turn(), turn(Stone::White, 1));
+    board.jump(0)?;
+    assert_eq!(board.get(Point::ORIGIN), None);
+    board.jump(3)?;
+    assert_eq!(board.get(Point::ORIGIN), Some(Stone::White));
+
+    assert!(matches!(
+        board.set(Point::PASS, Stone::Black),
+        Err(SetError::OutOfBounds)
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_candidate_moves() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_square(19);
+    assert!(board.candidate_moves().is_empty());
+
+    board.set(Point::ORIGIN, Stone::Black)?;
+    let candidates = board.candidate_moves();
+    assert_eq!(candidates.len(), 24);
+    assert!(!candidates.contains(&Point::ORIGIN));
+    assert!(candidates.contains(&Point::new(-2, 2)));
+
+    board.set(Point::new(1, 0), Stone::White)?;
+    assert_eq!(board.candidate_moves().len(), 28);
+
+    let corner = board.empty_neighbors_within(Point::new(9, 9), 1);
+    assert_eq!(
+        corner,
+        [
+            Point::new(8, 8),
+            Point::new(9, 8),
+            Point::new(8, 9),
+            Point::new(9, 9)
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_region() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_square(3);
+    board.set(Point::new(0, 0), Stone::Black)?;
+    board.set(Point::new(1, -1), Stone::White)?;
+
+    let region: Vec<_> = board.region(Point::new(0, -1), (3, 2), false).collect();
+    assert_eq!(
+        region,
+        [
+            (Point::new(0, -1), None),
+            (Point::new(1, -1), Some(Stone::White)),
+            (Point::new(2, -1), None),
+            (Point::new(0, 0), Some(Stone::Black)),
+            (Point::new(1, 0), None),
+            (Point::new(2, 0), None),
+        ]
+    );
+
+    let in_bounds: Vec<_> = board
+        .region(Point::new(0, -1), (3, 2), true)
+        .map(|(point, _)| point)
+        .collect();
+    assert_eq!(
+        in_bounds,
+        [
+            Point::new(0, -1),
+            Point::new(1, -1),
+            Point::new(0, 0),
+            Point::new(1, 0)
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_move_index_of() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_infinite();
+    board.set(Point::new(0, 0), Stone::Black)?;
+    board.set(Point::new(1, 1), Stone::White)?;
+    board.set(Point::new(2, 2), Stone::White)?;
+
+    assert_eq!(board.move_index_of(Point::new(1, 1)), Some(1));
+    assert_eq!(board.move_index_of(Point::new(2, 2)), Some(2));
+    assert_eq!(board.move_index_of(Point::new(3, 3)), None);
+    assert_eq!(board.played_at(Point::new(1, 1)), Some(Stone::White));
+
+    board.unset();
+    assert_eq!(board.move_index_of(Point::new(2, 2)), None);
+    assert_eq!(board.played_at(Point::new(2, 2)), None);
+    Ok(())
+}
+
+#[test]
+fn test_moves_since() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_infinite();
+    board.set(Point::new(0, 0), Stone::Black)?;
+    board.set(Point::new(1, 0), Stone::White)?;
+    board.set(Point::new(2, 0), Stone::White)?;
+    board.unset();
+
+    assert_eq!(board.moves_since(0), board.past_record());
+    assert_eq!(board.moves_since(1), [(Point::new(1, 0), Stone::White)]);
+    assert!(board.moves_since(2).is_empty());
+    assert!(board.moves_since(3).is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_apply_moves() {
+    let moves = [
+        (Point::new(0, 0), Stone::Black),
+        (Point::new(1, 0), Stone::White),
+        (Point::new(10, 0), Stone::White),
+        (Point::new(2, 0), Stone::Black),
+    ];
+
+    let mut board = Board::new_square(19);
+    let res = board.apply_moves(&moves);
+    assert!(matches!(res, Err((2, SetError::OutOfBounds))));
+    assert_eq!(board.past_record(), &moves[..2]);
+
+    assert!(matches!(
+        Board::from_moves(Bounds::Rect(19, 19), &moves),
+        Err((2, SetError::OutOfBounds))
+    ));
+    let board = Board::from_moves(Bounds::Infinite, &moves).unwrap();
+    assert_eq!(board.past_record(), moves);
+}
+
+#[test]
+fn test_collect() {
+    let moves = [
+        (Point::new(0, 0), Stone::Black),
+        (Point::new(1, 0), Stone::White),
+        (Point::new(0, 0), Stone::White),
+        (Point::new(2, 0), Stone::White),
+    ];
+
+    let board: Board = moves[..2].iter().copied().collect();
+    assert_eq!(board.past_record(), &moves[..2]);
+
+    let mut board: Board = moves.into_iter().collect();
+    assert_eq!(board.past_record(), [moves[0], moves[1], moves[3]]);
+
+    board.extend([
+        (Point::new(1, 0), Stone::Black),
+        (Point::new(3, 0), Stone::Black),
+    ]);
+    assert_eq!(board.total_count(), 4);
+    assert_eq!(board.get(Point::new(3, 0)), Some(Stone::Black));
+}
+
+#[test]
+fn test_line_through() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_square(5);
+    board.set(Point::new(1, 1), Stone::White)?;
+
+    let line: Vec<_> = board
+        .line_through(Point::ORIGIN, Direction::DiagDown)
+        .collect();
+    assert_eq!(
+        line,
+        [
+            (Point::new(0, 0), None),
+            (Point::new(1, 1), Some(Stone::White)),
+            (Point::new(2, 2), None)
+        ]
+    );
+
+    let line: Vec<_> = board
+        .line_through_rev(Point::new(1, 0), Direction::Horizontal)
+        .map(|(point, _)| point)
+        .collect();
+    assert_eq!(
+        line,
+        [
+            Point::new(1, 0),
+            Point::new(0, 0),
+            Point::new(-1, 0),
+            Point::new(-2, 0)
+        ]
+    );
+
+    let board = Board::new_infinite();
+    let edge = Point::new(i32::MAX - 1, 0);
+    assert_eq!(board.line_through(edge, Direction::Horizontal).count(), 2);
+    Ok(())
+}
+
+#[test]
+fn test_unset_turn() -> Result<(), Box<dyn Error>> {
+    let moves = [
+        (Point::new(0, 0), Stone::Black),
+        (Point::new(1, 0), Stone::White),
+        (Point::new(2, 0), Stone::White),
+        (Point::new(3, 0), Stone::Black),
+    ];
+    let mut board = Board::from_moves(Bounds::Infinite, &moves).unwrap();
+
+    assert_eq!(board.unset_turn(), [moves[3]]);
+    assert_eq!(board.index(), 3);
+    assert_eq!(board.unset_turn(), [moves[2], moves[1]]);
+    assert_eq!(board.index(), 1);
+    assert_eq!(board.unset_turn(), [moves[0]]);
+    assert_eq!(board.index(), 0);
+    assert!(board.unset_turn().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_raw_board_reader() {
+    let mut board = RawBoard::new();
+    for i in 0..100 {
+        let _ = board.set(Point::new(i * 7 - 300, i % 5), Stone::White);
+    }
+
+    let mut reader = board.reader();
+    for x in -320..400 {
+        for y in -1..6 {
+            let point = Point::new(x, y);
+            assert_eq!(reader.get(point), board.get(point));
+        }
+    }
+}
+
+#[test]
+fn test_raw_board_chunks() {
+    let mut board = RawBoard::new();
+    let stones = [Stone::Black, Stone::White, Stone::White]
+        .into_iter()
+        .cycle();
+    for (i, stone) in (0..100).zip(stones) {
+        let _ = board.set(Point::new(i * 11 - 500, i * 3 - 40), stone);
+    }
+
+    let chunks: Vec<_> = board
+        .iter_chunks()
+        .map(|(chunk_i, &words)| (chunk_i, words))
+        .collect();
+    assert!(chunks.windows(2).all(|w| w[0].0 < w[1].0));
+
+    let restored = RawBoard::from_chunks(chunks);
+    for x in -520..620 {
+        for y in (-43..260).step_by(3) {
+            let point = Point::new(x, y);
+            assert_eq!(restored.get(point), board.get(point));
+        }
+    }
+    assert_eq!(restored.count_by_color(), board.count_by_color());
+}
+
+#[test]
+fn test_raw_board_merge() {
+    let mut board = RawBoard::new();
+    let mut other = RawBoard::new();
+    for i in 0..50 {
+        let _ = board.set(Point::new(i * 5, -i), Stone::Black);
+        let _ = other.set(Point::new(-i * 5 - 5, i), Stone::White);
+    }
+    // Stones of the same color may overlap.
+    let _ = other.set(Point::new(5, -1), Stone::Black);
+
+    assert_eq!(board.merge(&other), Ok(()));
+    assert_eq!(board.count_by_color(), (50, 50));
+    assert_eq!(board.get(Point::new(-250, 49)), Some(Stone::White));
+    assert_eq!(board.get(Point::new(245, -49)), Some(Stone::Black));
+
+    let mut conflicting = RawBoard::new();
+    let _ = conflicting.set(Point::new(-100, 20), Stone::Black);
+    let _ = conflicting.set(Point::new(10, -2), Stone::White);
+    let _ = conflicting.set(Point::new(1, 1), Stone::White);
+    assert_eq!(board.merge(&conflicting), Err(Point::new(10, -2)));
+    // Nothing is copied on failure.
+    assert_eq!(board.get(Point::new(1, 1)), None);
+    assert_eq!(board.count_by_color(), (50, 50));
+}
+
+#[test]
+fn test_chunk_rects_tile() {
+    // The 16 chunks nearest to the origin cover exactly [-32, 32) squared.
+    let mut coverage = vec![0; 64 * 64];
+    for chunk_i in 0..16 {
+        for (min, max) in RawBoard::chunk_rects(chunk_i) {
+            for y in min.y..=max.y {
+                for x in min.x..=max.x {
+                    let point = Point::new(x, y);
+                    assert_eq!(point.index() >> (CHUNK_SIZE_BITS * 2), chunk_i);
+                    coverage[(y + 32) as usize * 64 + (x + 32) as usize] += 1;
+                }
+            }
+        }
+    }
+    assert!(coverage.iter().all(|&n| n == 1));
+
+    let far = RawBoard::chunk_rects(u64::MAX >> (CHUNK_SIZE_BITS * 2));
+    let corner = (
+        Point::new(i32::MIN, i32::MIN),
+        Point::new(i32::MIN + 7, i32::MIN + 7),
+    );
+    assert!(far.contains(&corner));
+}
+
+#[test]
+fn test_star_points() {
+    let points = star_points(Bounds::Rect(19, 19));
+    assert_eq!(points.len(), 9);
+    assert!(points.contains(&Point::new(-6, -6)));
+    assert!(points.contains(&Point::ORIGIN));
+    assert!(points.contains(&Point::new(6, 0)));
+
+    assert_eq!(star_points(Bounds::Rect(13, 13)).len(), 5);
+    assert_eq!(star_points(Bounds::Rect(8, 8)).len(), 4);
+    assert!(star_points(Bounds::Rect(5, 5)).is_empty());
+    assert!(star_points(Bounds::Infinite).is_empty());
+
+    assert!(is_star_point(Bounds::Rect(19, 19), Point::new(6, 6)));
+    assert!(!is_star_point(Bounds::Rect(19, 19), Point::new(12, 12)));
+    assert!(is_star_point(Bounds::Infinite, Point::new(-12, 18)));
+    assert!(!is_star_point(Bounds::Infinite, Point::new(3, 6)));
+
+    // The test agrees with the list on boards of every shape.
+    for (width, height) in [(19, 19), (13, 13), (8, 8), (5, 5), (15, 9), (16, 20)] {
+        let bounds = Bounds::Rect(width, height);
+        let points = star_points(bounds);
+        for point in Point::ORIGIN.disk(10) {
+            assert_eq!(
+                is_star_point(bounds, point),
+                points.contains(&point),
+                "{point} on {width} by {height}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_position_equal() -> Result<(), Box<dyn Error>> {
+    let a = Board::from_moves(
+        Bounds::Infinite,
+        &[
+            (Point::ORIGIN, Stone::Black),
+            (Point::new(1, 0), Stone::White),
+            (Point::new(2, 1), Stone::White),
+        ],
+    )
+    .unwrap();
+    let mut b = Board::from_moves(
+        Bounds::Infinite,
+        &[
+            (Point::ORIGIN, Stone::Black),
+            (Point::new(2, 1), Stone::White),
+            (Point::new(1, 0), Stone::White),
+        ],
+    )
+    .unwrap();
+    assert_ne!(a, b);
+    assert!(a.position_equal(&b));
+
+    b.unset();
+    assert!(!a.position_equal(&b));
+    Ok(())
+}
+
+#[test]
+fn test_moves_equal() {
+    let moves = [
+        (Point::ORIGIN, Stone::Black),
+        (Point::new(1, 0), Stone::White),
+    ];
+    let bounded = Board::from_moves(Bounds::Rect(19, 19), &moves).unwrap();
+    let infinite = Board::from_moves(Bounds::Infinite, &moves).unwrap();
+    assert!(bounded.moves_equal(&infinite));
+    assert_ne!(bounded, infinite);
+
+    let reordered = Board::from_moves(Bounds::Infinite, &[moves[1], moves[0]]).unwrap();
+    assert!(!reordered.moves_equal(&infinite));
+}
+
+#[test]
+fn test_symmetric_equal() -> Result<(), Box<dyn Error>> {
+    assert_eq!(
+        Point::new(2, 1).transform(Symmetry::Rotate90),
+        Point::new(-1, 2)
+    );
+    assert_eq!(
+        Point::new(2, 1).transform(Symmetry::AntiTranspose),
+        Point::new(-1, -2)
+    );
+
+    let moves = [
+        (Point::new(-1, -1), Stone::Black),
+        (Point::new(1, -1), Stone::White),
+        (Point::new(1, 0), Stone::White),
+    ];
+    let a = Board::from_moves(Bounds::Rect(4, 4), &moves).unwrap();
+
+    // Reflected across the vertical axis of the 4 by 4 board, whose center
+    // lies between x = -1 and x = 0.
+    let reflected = moves.map(|(p, stone)| (Point::new(-1 - p.x, p.y), stone));
+    let mut b = Board::from_moves(Bounds::Rect(4, 4), &reflected).unwrap();
+    assert!(!a.position_equal(&b));
+    assert!(a.symmetric_equal(&b));
+
+    b.set(Point::new(0, 0), Stone::Black)?;
+    assert!(!a.symmetric_equal(&b));
+    Ok(())
+}
+
+#[test]
+fn test_bitboards() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_infinite();
+    let moves = [
+        (Point::ORIGIN, Stone::Black),
+        (Point::new(-1, 3), Stone::White),
+        (Point::new(3, 3), Stone::Black),
+        (Point::new(4, 0), Stone::White),
+        (Point::new(-20, 7), Stone::Black),
+    ];
+    board.apply_moves(&moves).map_err(|(_, e)| e)?;
+
+    let mut stones = Vec::new();
+    for (block_i, black, white) in board.bitboards() {
+        for bit in 0..64 {
+            let point = Point::from_index(block_i << 6 | bit);
+            if black >> bit & 1 != 0 {
+                stones.push((point, Stone::Black));
+            }
+            if white >> bit & 1 != 0 {
+                stones.push((point, Stone::White));
+            }
+        }
+    }
+    assert_eq!(stones.len(), moves.len());
+    for stone in moves {
+        assert!(stones.contains(&stone));
+    }
+
+    // The first three stones are zigzag-encoded into the first 8 by 8 block,
+    // but (4, 0) is encoded as (8, 0), which is outside it.
+    let (block_i, black, white) = board.bitboards().next().unwrap();
+    assert_eq!(block_i, 0);
+    assert_eq!((black.count_ones(), white.count_ones()), (2, 1));
+    let blocks: Vec<u64> = board.bitboards().map(|(i, _, _)| i).collect();
+    assert!(blocks.windows(2).all(|w| w[0] < w[1]));
+    Ok(())
+}
+
+#[test]
+fn test_occupied_bounds() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_infinite();
+    assert_eq!(board.occupied_bounds(), None);
+
+    board.set(Point::new(1, 1), Stone::Black)?;
+    board.set(Point::new(-2, 0), Stone::White)?;
+    board.set(Point::new(0, 3), Stone::White)?;
+    board.set(Point::new(0, 1), Stone::Black)?;
+    assert_eq!(
+        board.occupied_bounds(),
+        Some((Point::new(-2, 0), Point::new(1, 3)))
+    );
+
+    // An inner stone leaves the bounds as they are.
+    board.unset();
+    assert_eq!(
+        board.occupied_bounds(),
+        Some((Point::new(-2, 0), Point::new(1, 3)))
+    );
+
+    // A stone on the edge shrinks them.
+    board.unset();
+    assert_eq!(
+        board.occupied_bounds(),
+        Some((Point::new(-2, 0), Point::new(1, 1)))
+    );
+    board.reset();
+    board.reset();
+    assert_eq!(
+        board.occupied_bounds(),
+        Some((Point::new(-2, 0), Point::new(1, 3)))
+    );
+
+    board.jump(1)?;
+    assert_eq!(
+        board.occupied_bounds(),
+        Some((Point::new(1, 1), Point::new(1, 1)))
+    );
+    board.jump(0)?;
+    assert_eq!(board.occupied_bounds(), None);
+    board.jump(4)?;
+
+    // Passes don't count, and placing a stone after undoing drops the rest
+    // of the record.
+    board.pass();
+    board.jump(2)?;
+    board.set(Point::new(5, -5), Stone::White)?;
+    assert_eq!(
+        board.occupied_bounds(),
+        Some((Point::new(-2, -5), Point::new(5, 1)))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_snapshot_restore() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_infinite();
+    board.set(Point::new(0, 0), Stone::Black)?;
+    board.set(Point::new(1, 0), Stone::White)?;
+    board.set(Point::new(1, 1), Stone::White)?;
+    let turn = board.turn();
+    let bounds = board.occupied_bounds();
+
+    let snap = board.snapshot();
+    board.set(Point::new(-3, 4), Stone::Black)?;
+    board.pass();
+    board.set(Point::new(9, 9), Stone::White)?;
+    board.resign(Stone::Black);
+
+    board.restore(&snap)?;
+    let expected = Board::from_moves(
+        Bounds::Infinite,
+        &[
+            (Point::new(0, 0), Stone::Black),
+            (Point::new(1, 0), Stone::White),
+            (Point::new(1, 1), Stone::White),
+        ],
+    )
+    .unwrap();
+    assert_eq!(board, expected);
+    assert_eq!(board.total_count(), 3);
+    assert_eq!(board.get(Point::new(-3, 4)), None);
+    assert_eq!(board.turn(), turn);
+    assert_eq!(board.occupied_bounds(), bounds);
+    assert_eq!(board.outcome(), None);
+
+    // Stale and foreign snapshots are refused.
+    let snap = board.snapshot();
+    board.unset();
+    assert_eq!(board.restore(&snap), Err(RestoreError::Ahead));
+    board.set(Point::new(-5, -5), Stone::White)?;
+    assert_eq!(board.restore(&snap), Err(RestoreError::Mismatch));
+    let other = Board::from_moves(Bounds::Infinite, &[(Point::new(3, 3), Stone::Black)]).unwrap();
+    assert_eq!(
+        board.restore(&other.snapshot()),
+        Err(RestoreError::Mismatch)
+    );
+    assert_eq!(board.total_count(), 3);
+
+    let mut limited = Board::with_undo_limit(Bounds::Infinite, 1);
+    let snap = limited.snapshot();
+    limited.set(Point::ORIGIN, Stone::Black)?;
+    limited.set(Point::new(1, 0), Stone::White)?;
+    assert_eq!(limited.restore(&snap), Err(RestoreError::BelowFloor));
+    assert_eq!(limited.total_count(), 2);
+    Ok(())
+}
+
+#[test]
+fn test_hash() -> Result<(), Box<dyn Error>> {
+    let moves = [
+        (Point::ORIGIN, Stone::Black),
+        (Point::new(1, 0), Stone::White),
+    ];
+    let mut set = HashSet::new();
+    assert!(set.insert(Board::from_moves(Bounds::Infinite, &moves).unwrap()));
+    assert!(!set.insert(Board::from_moves(Bounds::Infinite, &moves).unwrap()));
+    assert!(set.insert(Board::from_moves(Bounds::Rect(19, 19), &moves).unwrap()));
+
+    // Undone moves are not part of the board.
+    let mut board = Board::from_moves(Bounds::Infinite, &moves).unwrap();
+    board.set(Point::new(2, 0), Stone::White)?;
+    assert!(set.insert(board));
+    let mut board = Board::from_moves(Bounds::Infinite, &moves).unwrap();
+    board.set(Point::new(2, 0), Stone::White)?;
+    board.unset();
+    assert!(!set.insert(board));
+    assert_eq!(set.len(), 3);
+    Ok(())
+}
+
+#[test]
+fn test_swap_sides() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_infinite();
+    assert!(!board.can_swap());
+    assert!(!board.swap_sides());
+
+    board.set(Point::ORIGIN, Stone::Black)?;
+    assert!(board.can_swap());
+    assert!(board.swap_sides());
+    assert!(board.is_swapped());
+    assert_eq!(board.get(Point::ORIGIN), Some(Stone::White));
+    let turn = Turn {
+        stone: Stone::Black,
+        remaining: 2,
+    };
+    assert_eq!(board.turn(), turn);
+    assert!(!board.swap_sides());
+    board.set(Point::new(1, 0), Stone::Black)?;
+    assert_eq!(board.validate_opening(), Ok(()));
+    board.unset();
+
+    let mut record = Vec::new();
+    board.save_record(&mut record)?;
+    assert!(String::from_utf8(record.clone())?.contains("\nOpening: Swapped\n"));
+    let loaded = Board::load_record(&record[..])?;
+    assert!(loaded.is_swapped());
+    assert_eq!(loaded.turn(), turn);
+    assert_eq!(loaded, board);
+
+    // Inverting the colors turns it back into a game opened by black.
+    let mut inverted = board.clone();
+    inverted.invert_colors();
+    assert!(!inverted.is_swapped());
+    assert_eq!(inverted.validate_opening(), Ok(()));
+    inverted.invert_colors();
+    assert_eq!(inverted, board);
+
+    // A new opening stone starts over without the swap.
+    board.unset();
+    assert!(!board.is_swapped());
+    board.set(Point::ORIGIN, Stone::Black)?;
+    assert!(!board.is_swapped());
+    assert!(board.can_swap());
+
+    // A white opening stone placed directly is not a swap.
+    let mut board = Board::new_infinite();
+    board.set(Point::ORIGIN, Stone::White)?;
+    assert!(!board.is_swapped());
+    assert_eq!(board.turn().stone, Stone::White);
+    assert!(board.validate_opening().is_err());
+
+    // Too late once white has replied.
+    let mut board = Board::new_infinite();
+    board.set(Point::ORIGIN, Stone::Black)?;
+    board.set(Point::new(1, 0), Stone::White)?;
+    assert!(!board.swap_sides());
+    assert_eq!(board.get(Point::ORIGIN), Some(Stone::Black));
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "move 3: White played (1, 1) on the turn of Black")]
+fn test_builder_wrong_turn() {
+    BoardBuilder::infinite()
+        .black(Point::ORIGIN)
+        .white_pair("K10", "K11")
+        .white(Point::new(1, 1));
+}
+
+#[test]
+fn test_move_list() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_square(19);
+    board.set(Point::ORIGIN, Stone::Black)?;
+    board.set(Point::new(1, 0), Stone::White)?;
+    board.pass();
+    board.set(Point::new(-9, 9), Stone::Black)?;
+
+    let moves = board.to_move_list();
+    assert_eq!(
+        moves,
+        [
+            (0, 0, Stone::Black),
+            (1, 0, Stone::White),
+            (i32::MIN, i32::MIN, Stone::White),
+            (-9, 9, Stone::Black),
+        ]
+    );
+    assert_eq!(Board::from_move_list(Bounds::Rect(19, 19), &moves)?, board);
+
+    assert!(matches!(
+        Board::from_move_list(Bounds::Rect(17, 17), &moves),
+        Err(SetError::OutOfBounds)
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_turns() -> Result<(), Box<dyn Error>> {
+    let mut board = BoardBuilder::infinite()
+        .black(Point::ORIGIN)
+        .white_pair(Point::new(1, 0), Point::new(2, 0))
+        .black_pair(Point::new(3, 0), Point::new(4, 0))
+        .white(Point::new(5, 0))
+        .build();
+    // The last turn is incomplete.
+    assert_eq!(board.turn_count(), 3);
+
+    for (turn, index) in [(0, 0), (1, 1), (2, 3), (3, 5), (4, 6)] {
+        board.jump_to_turn(turn)?;
+        assert_eq!(board.index(), index);
+        assert_eq!(board.turn_count(), turn.min(3));
+    }
+    assert!(board.jump_to_turn(5).is_err());
+
+    let lens: Vec<usize> = board.iter_turns().map(<[_]>::len).collect();
+    assert_eq!(lens, [1, 2, 2, 1]);
+    assert_eq!(
+        board.iter_turns().nth(1),
+        Some(
+            &[
+                (Point::new(1, 0), Stone::White),
+                (Point::new(2, 0), Stone::White)
+            ][..]
+        )
+    );
+
+    // A pass ends the turn early.
+    board.jump_to_turn(2)?;
+    board.pass();
+    board.set(Point::new(0, 1), Stone::White)?;
+    assert_eq!(board.turn_count(), 3);
+    board.jump_to_turn(3)?;
+    assert_eq!(board.index(), 4);
+    Ok(())
+}
+
+#[test]
+fn test_get_many() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_infinite();
+    for (x, y) in [(0, 0), (1, 0), (-20, 3), (40, -40), (15, 16)] {
+        board.set(Point::new(x, y), board.turn().stone)?;
+    }
+
+    let points: Vec<Point> = (-45..=45)
+        .step_by(5)
+        .flat_map(|x| [Point::new(x, -x), Point::new(x / 3, 0), Point::new(-20, 3)])
+        .chain([Point::new(15, 16), Point::ORIGIN, Point::new(40, -40)])
+        .collect();
+    let expected: Vec<_> = points.iter().map(|&p| board.get(p)).collect();
+    assert_eq!(board.get_many(&points), expected);
+    assert_eq!(expected.iter().flatten().count(), 2 + 2 + 19 + 3);
+    assert!(board.get_many(&[]).is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_next_to_play() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_infinite();
+    let mut states = vec![(board.next_to_play(), board.is_first_of_turn())];
+    for (x, stone) in [(0, Stone::Black), (1, Stone::White), (2, Stone::White)] {
+        board.set(Point::new(x, 0), stone)?;
+        states.push((board.next_to_play(), board.is_first_of_turn()));
+    }
+    board.set(Point::new(3, 0), Stone::Black)?;
+    states.push((board.next_to_play(), board.is_first_of_turn()));
+    board.pass();
+    states.push((board.next_to_play(), board.is_first_of_turn()));
+    assert_eq!(
+        states,
+        [
+            (Stone::Black, true),
+            (Stone::White, true),
+            (Stone::White, false),
+            (Stone::Black, true),
+            (Stone::Black, false),
+            (Stone::White, true),
+        ]
+    );
+
+    board.jump(1)?;
+    board.set_turn(Turn {
+        stone: Stone::White,
+        remaining: 1,
+    })?;
+    assert_eq!(board.next_to_play(), Stone::White);
+    assert!(!board.is_first_of_turn());
+
+    #[allow(deprecated)]
+    let inferred = board.infer_turn();
+    assert_eq!(inferred, (Stone::White, false));
+    Ok(())
+}
+
+#[test]
+fn test_can_place() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_square(3);
+    board.set(Point::ORIGIN, Stone::Black)?;
+
+    assert!(matches!(
+        board.can_place(Point::ORIGIN),
+        Err(SetError::Occupied)
+    ));
+    assert!(matches!(
+        board.can_place(Point::new(2, 0)),
+        Err(SetError::OutOfBounds)
+    ));
+    assert!(matches!(
+        board.can_place(Point::PASS),
+        Err(SetError::OutOfBounds)
+    ));
+    assert!(board.can_place(Point::new(1, -1)).is_ok());
+    // Nothing is placed.
+    assert_eq!(board.get(Point::new(1, -1)), None);
+    assert_eq!(board.total_count(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_neighbors_in_bounds() {
+    // The top left corner of the board spans [-2, 2] squared.
+    let board = Board::new_square(5);
+    let corner = Point::new(-2, -2);
+    assert_eq!(
+        board.neighbors_in_bounds(corner),
+        [Point::new(-1, -2), Point::new(-2, -1), Point::new(-1, -1)]
+    );
+    assert_eq!(board.neighbors_in_bounds(Point::new(0, 2)).len(), 5);
+    assert_eq!(
+        board.neighbors_in_bounds(Point::ORIGIN),
+        Point::ORIGIN.neighbors8()
+    );
+
+    let edge = Point::new(i32::MIN, 0);
+    assert!(!Board::new_infinite()
+        .neighbors_in_bounds(Point::new(i32::MIN + 1, i32::MIN + 1))
+        .contains(&Point::PASS));
+    assert_eq!(edge.neighbors()[1], Point::new(i32::MAX, 0));
+}
+
+#[test]
+fn test_limited_bounds() -> Result<(), Box<dyn Error>> {
+    let bounds = Bounds::Limited(1000);
+    let mut board = Board::new(bounds);
+    board.set(Point::new(1000, -1000), Stone::Black)?;
+    board.set(Point::new(-1000, 0), Stone::White)?;
+    for point in [
+        Point::new(1001, 0),
+        Point::new(0, -1001),
+        Point::new(i32::MAX, i32::MAX),
+        Point::PASS,
+    ] {
+        assert!(matches!(
+            board.set(point, Stone::White),
+            Err(SetError::OutOfBounds)
+        ));
+    }
+
+    let far = Point::new(i32::MIN, 5000);
+    assert_eq!(bounds.clamp_point(far), Point::new(-1000, 1000));
+    assert!(Bounds::Limited(u32::MAX).contains(far));
+    assert!(!Bounds::Limited(u32::MAX).contains(Point::PASS));
+    assert!(!Bounds::Infinite.contains(Point::PASS));
+    assert!(!Bounds::Limited(0).contains(Point::new(1, 0)));
+
+    let record = board.to_string_record();
+    assert!(record.contains("Board: Limited(1000)"));
+    assert_eq!(Board::from_str_record(&record)?, board);
+    Ok(())
+}
+
+#[test]
+fn test_undo_limit() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::with_undo_limit(Bounds::Infinite, 3);
+    for x in 0..5 {
+        board.set(Point::new(x, 0), board.turn().stone)?;
+    }
+    assert_eq!(board.undo_floor(), 2);
+    assert_eq!(board.total_count(), 5);
+    assert!(!board.can_swap());
+
+    // The last three moves can be undone and redone, but no more.
+    let err = board.jump(1).unwrap_err();
+    assert_eq!((err.index, err.len, err.floor), (1, 5, 2));
+    board.jump(2)?;
+    assert_eq!(board.unset(), None);
+    assert!(board.unset_turn().is_empty());
+    assert!(board.jump_to_turn(1).is_err());
+    board.jump(5)?;
+    assert_eq!(board.unset_turn().len(), 2);
+    assert_eq!(board.unset(), Some((Point::new(2, 0), Stone::White)));
+    assert_eq!(board.unset(), None);
+    assert_eq!(board.index(), 2);
+
+    // Moves made after undoing raise the floor from the new index on.
+    for y in 1..4 {
+        board.set(Point::new(0, y), board.turn().stone)?;
+    }
+    assert_eq!(board.total_count(), 5);
+    assert_eq!(board.undo_floor(), 2);
+    board.set(Point::new(0, 4), board.turn().stone)?;
+    assert_eq!(board.undo_floor(), 3);
+
+    // Moves below the floor are still saved.
+    let loaded = Board::from_str_record(&board.to_string_record())?;
+    assert_eq!(loaded.past_record(), board.past_record());
+
+    let mut board = Board::with_undo_limit(Bounds::Infinite, 0);
+    board.set(Point::ORIGIN, Stone::Black)?;
+    assert_eq!(board.unset(), None);
+    Ok(())
+}
+
+#[test]
+fn test_set_by_index() -> Result<(), Box<dyn Error>> {
+    let points = [Point::ORIGIN, Point::new(-3, 7), Point::new(9, -9)];
+    let mut by_point = Board::new_square(19);
+    let mut by_index = Board::new_square(19);
+    for point in points {
+        let stone = by_point.turn().stone;
+        by_point.set(point, stone)?;
+        by_index.set_by_index(point.index(), stone)?;
+    }
+    assert_eq!(by_index, by_point);
+    assert!(matches!(
+        by_index.set_by_index(Point::new(-3, 7).index(), Stone::Black),
+        Err(SetError::Occupied)
+    ));
+    assert!(matches!(
+        by_index.set_by_index(Point::new(10, 0).index(), Stone::Black),
+        Err(SetError::OutOfBounds)
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_position_save_load() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_square(19);
+    for y in -9..=9 {
+        for x in -9..=9 {
+            if (x * 7 + y * 3) % 5 != 0 {
+                board.set(Point::new(x, y), board.turn().stone)?;
+            }
+        }
+    }
+    board.set_win_rule(WinRule::ExactlySix);
+    let mut buf = Vec::new();
+    board.save_position(&mut buf)?;
+
+    let loaded = Board::load_position(&buf[..])?;
+    assert!(loaded.position_equal(&board));
+    assert_eq!(loaded.bounds(), board.bounds());
+    assert_eq!(loaded.win_rule(), WinRule::ExactlySix);
+    assert_eq!(loaded.turn(), board.turn());
+    assert_eq!(loaded.occupied_bounds(), board.occupied_bounds());
+    assert_eq!(loaded.is_terminal(), board.outcome().is_some());
+    assert_eq!(loaded.total_count(), board.stones().count());
+
+    let mut empty = Vec::new();
+    Board::new_infinite().save_position(&mut empty)?;
+    assert_eq!(Board::load_position(&empty[..])?.total_count(), 0);
+
+    let last = buf.len() - 5;
+    buf[last] ^= 1;
+    assert!(matches!(
+        Board::load_position(&buf[..]),
+        Err(LoadPositionError::Data("wrong checksum"))
+    ));
+    assert!(Board::load_position(&buf[..3]).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_count_stones_in() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_infinite();
+    for i in 0..400 {
+        let point = Point::new(i * 7 % 41 - 20, i * 13 % 37 - 18);
+        if board.get(point).is_none() {
+            board.set(point, board.turn().stone)?;
+        }
+    }
+    board.set(Point::new(1000, -1000), Stone::Black)?;
+
+    let regions = [
+        (Point::new(-20, -18), (41, 37)),
+        (Point::new(-5, -3), (9, 11)),
+        (Point::new(3, 2), (16, 16)),
+        (Point::new(-17, -9), (1, 30)),
+        (Point::new(-30, -30), (60, 5)),
+        (Point::new(990, -1010), (20, 20)),
+        (Point::ORIGIN, (0, 5)),
+    ];
+    for (top_left, size) in regions {
+        for stone in [None, Some(Stone::Black), Some(Stone::White)] {
+            let expected = board
+                .region(top_left, size, false)
+                .filter(|&(_, slot)| slot.is_some() && (stone.is_none() || slot == stone))
+                .count();
+            assert_eq!(board.count_stones_in(top_left, size, stone), expected);
+        }
+    }
+    assert_eq!(
+        board.count_stones_in(Point::new(-20, -18), (41, 37), None),
+        board.total_count() - 1
+    );
+
+    // Rectangles running past the edges of the coordinate space.
+    let far = Point::new(i32::MAX - 1, i32::MAX - 1);
+    board.set(far, Stone::White)?;
+    assert_eq!(board.count_stones_in(far, (u32::MAX, 5), None), 1);
+    Ok(())
+}
+
+#[test]
+fn test_transform() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_infinite();
+    for x in 0..6 {
+        board.set(Point::new(x, 2), Stone::Black)?;
+    }
+    board.pass();
+    let rotated = board.transform(Symmetry::Rotate90);
+    let line = (0..6).map(|y| Point::new(-2, y));
+    assert!(rotated.past_record()[..6].iter().map(|&(p, _)| p).eq(line));
+    assert_eq!(rotated.past_record()[6], board.past_record()[6]);
+    let Some(Outcome::Win { line, .. }) = rotated.outcome() else {
+        panic!("expected a win");
+    };
+    assert!(line.iter().all(|p| p.x == -2));
+    assert_eq!(
+        rotated.transform(Symmetry::Rotate270).past_record(),
+        board.past_record()
+    );
+
+    // An even rect maps onto itself, with its sides swapped if need be.
+    let mut board = Board::new(Bounds::Rect(4, 7));
+    board.set(Point::new(-2, -3), Stone::Black)?;
+    board.set(Point::new(1, 0), Stone::White)?;
+    board.set(Point::new(0, 3), Stone::White)?;
+    board.unset();
+    for sym in Symmetry::ALL {
+        let transformed = board.transform(sym);
+        assert!(transformed.symmetric_equal(&board));
+        assert_eq!(transformed.total_count(), 3);
+        assert_eq!(transformed.index(), 2);
+        let twice = transformed.transform(sym);
+        match sym {
+            Symmetry::Rotate90 | Symmetry::Rotate270 => {
+                assert_eq!(twice, board.transform(Symmetry::Rotate180))
+            }
+            _ => assert_eq!(twice, board),
+        }
+    }
+    assert_eq!(
+        board.transform(Symmetry::Transpose).bounds(),
+        Bounds::Rect(7, 4)
+    );
+    assert_eq!(
+        board.transform(Symmetry::FlipHorizontal).past_record()[0],
+        (Point::new(1, -3), Stone::Black)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_is_occupied() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_square(19);
+    assert!(!board.is_occupied(Point::ORIGIN));
+    board.set(Point::ORIGIN, Stone::Black)?;
+    board.pass();
+    assert!(board.is_occupied(Point::ORIGIN));
+    // A pass is not a stone on the board.
+    assert!(!board.is_occupied(Point::PASS));
+    board.unset();
+    board.unset();
+    assert!(!board.is_occupied(Point::ORIGIN));
+    Ok(())
+}
+
+#[test]
+fn test_is_empty_at() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_square(19);
+    board.set(Point::new(2, 3), Stone::Black)?;
+    assert!(!board.is_empty_at(Point::new(2, 3)));
+    assert!(board.is_empty_at(Point::new(3, 2)));
+    // Points out of bounds are empty, but a stone can't be placed there.
+    assert!(board.is_empty_at(Point::new(10, 0)));
+    assert!(board.can_place(Point::new(10, 0)).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_invert_colors() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_square(19);
+    let moves = [(0, 0), (1, 0), (1, 1), (-9, 9), (9, -9), (-3, 4)].map(|(x, y)| Point::new(x, y));
+    for (i, &point) in moves.iter().enumerate() {
+        let stone = if matches!(i, 0 | 3 | 4) {
+            Stone::Black
+        } else {
+            Stone::White
+        };
+        board.set(point, stone)?;
+    }
+    board.pass();
+    let record = board.past_record().to_vec();
+    board.unset();
+    board.unset();
+    let before = moves.map(|point| board.get(point));
+    let turn = board.turn();
+
+    board.invert_colors();
+    for (point, stone) in moves.into_iter().zip(before) {
+        assert_eq!(board.get(point), stone.map(Stone::opposite));
+    }
+    assert_eq!(board.count_by_color(), (2, 3));
+    let inverted: Vec<_> = record
+        .iter()
+        .map(|&(point, stone)| (point, stone.opposite()))
+        .collect();
+    assert_eq!(board.turn().stone, turn.stone.opposite());
+    // The moves undone are inverted as well.
+    board.fast_forward();
+    assert_eq!(board.past_record(), inverted);
+
+    // A win changes sides too.
+    let mut board = Board::new_infinite();
+    for x in 0..6 {
+        board.set(Point::new(x, 0), Stone::Black)?;
+    }
+    board.invert_colors();
+    assert!(matches!(
+        board.cached_outcome(),
+        Some(Outcome::Win {
+            stone: Stone::White,
+            ..
+        })
+    ));
+    assert_eq!(board.cached_outcome(), board.outcome());
+    Ok(())
+}
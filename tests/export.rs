@@ -0,0 +1,90 @@
+use std::{
+    env,
+    error::Error,
+    fs::{self, File},
+    io::BufReader,
+    path::Path,
+    process::Command,
+};
+
+use c6::*;
+
+const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/record.c6");
+
+fn export(format: &str) -> Result<(bool, String), Box<dyn Error>> {
+    export_file(FIXTURE.as_ref(), format)
+}
+
+fn export_file(path: &Path, format: &str) -> Result<(bool, String), Box<dyn Error>> {
+    let output = Command::new(env!("CARGO_BIN_EXE_c6"))
+        .arg("export")
+        .arg(path)
+        .args(["--format", format])
+        .output()?;
+    Ok((output.status.success(), String::from_utf8(output.stdout)?))
+}
+
+// Exports a board saved to a temporary file as ASCII.
+fn export_ascii(board: &Board, name: &str) -> Result<(bool, String), Box<dyn Error>> {
+    let path = env::temp_dir().join(format!("c6-export-{}-{name}.c6", std::process::id()));
+    board.save_record(File::create(&path)?)?;
+    let output = export_file(&path, "ascii");
+    fs::remove_file(&path)?;
+    output
+}
+
+#[test]
+fn test_export() -> Result<(), Box<dyn Error>> {
+    let board = Board::load_record(BufReader::new(File::open(FIXTURE)?))?;
+
+    let (ok, notation) = export("notation")?;
+    assert!(ok);
+    assert_eq!(notation, board.to_notation() + "\n");
+
+    // The full board is filled, one row of points per line.
+    let (ok, ascii) = export("ascii")?;
+    assert!(ok);
+    let rows: Vec<&str> = ascii.lines().collect();
+    assert_eq!(rows.len(), 19);
+    assert!(rows.iter().all(|&row| row == rows[0]));
+    assert_eq!(rows[0], ["X"; 19].join(" "));
+
+    let (ok, json) = export("json")?;
+    #[cfg(feature = "json")]
+    assert!(ok && json == board.to_json() + "\n");
+    #[cfg(not(feature = "json"))]
+    assert!(!ok && json.is_empty());
+
+    let (ok, output) = export("yaml")?;
+    assert!(!ok && output.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_export_ascii() -> Result<(), Box<dyn Error>> {
+    let board = BoardBuilder::new(Bounds::Rect(5, 3))
+        .black(Point::ORIGIN)
+        .white_pair(Point::new(-2, -1), Point::new(1, 1))
+        .build();
+    let (ok, ascii) = export_ascii(&board, "rect")?;
+    assert!(ok);
+    assert_eq!(ascii, "O . . . .\n. . X . .\n. . . O .\n");
+
+    // Unbounded boards are drawn within their stones.
+    let board = BoardBuilder::infinite()
+        .black(Point::new(10, 10))
+        .white_pair(Point::new(12, 10), Point::new(11, 11))
+        .build();
+    let (ok, ascii) = export_ascii(&board, "infinite")?;
+    assert!(ok);
+    assert_eq!(ascii, "X . O\n. O .\n");
+
+    // Distant stones are refused instead of drawing a huge board.
+    let board = BoardBuilder::infinite()
+        .black(Point::ORIGIN)
+        .white_pair(Point::new(5000, 0), Point::new(0, -5000))
+        .build();
+    let (ok, ascii) = export_ascii(&board, "huge")?;
+    assert!(!ok && ascii.is_empty());
+    Ok(())
+}
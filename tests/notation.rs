@@ -0,0 +1,34 @@
+use std::error::Error;
+
+use c6::*;
+
+#[test]
+fn test_to_notation() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_square(19);
+    assert_eq!(board.to_notation(), "");
+
+    board.set(Point::ORIGIN, Stone::Black)?;
+    assert_eq!(board.to_notation(), "1. J10");
+
+    board.set(Point::new(1, 0), Stone::White)?;
+    board.set(Point::new(1, 1), Stone::White)?;
+    board.set(Point::new(-1, -1), Stone::Black)?;
+    board.pass();
+    board.set(Point::new(9, -9), Stone::White)?;
+    assert_eq!(board.to_notation(), "1. J10 / 2. K10 K11 3. I9 pass 4. S1");
+
+    let mut board = Board::new_infinite();
+    board.set(Point::new(-10, 0), Stone::Black)?;
+    board.set(Point::new(0, 0), Stone::White)?;
+    assert_eq!(board.to_notation(), "1. (-10, 0) / 2. J10");
+
+    // A pass ends a turn even if the same side plays on, as in navigation.
+    let mut board = Board::new_square(19);
+    board.set(Point::ORIGIN, Stone::Black)?;
+    board.pass();
+    board.set(Point::new(1, 0), Stone::White)?;
+    board.set(Point::new(1, 1), Stone::White)?;
+    assert_eq!(board.to_notation(), "1. J10 / 2. pass 3. K10 K11");
+    assert_eq!(board.turn_count(), 3);
+    Ok(())
+}
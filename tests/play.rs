@@ -0,0 +1,77 @@
+use std::error::Error;
+
+use c6::*;
+#[cfg(feature = "rand")]
+use rand::{rngs::StdRng, SeedableRng};
+
+#[cfg(feature = "rand")]
+#[test]
+fn test_random_vs_greedy() -> Result<(), Box<dyn Error>> {
+    for seed in 0..4 {
+        let mut board = Board::new_square(15);
+        let mut random = RandomMoveGen::new(StdRng::seed_from_u64(seed));
+        let mut greedy = GreedyMoveGen::default();
+        let outcome = run_game(&mut board, &mut random, &mut greedy, 200)?;
+        // The greedy side takes any six it is offered.
+        assert!(matches!(
+            outcome,
+            Some(Outcome::Win {
+                stone: Stone::White,
+                ..
+            })
+        ));
+        assert!(board.validate_opening().is_ok());
+    }
+
+    // A full board is a draw, and the game ends there.
+    let mut board = Board::new_square(3);
+    let mut black = RandomMoveGen::new(StdRng::seed_from_u64(0));
+    let mut white = RandomMoveGen::new(StdRng::seed_from_u64(1));
+    assert_eq!(
+        run_game(&mut board, &mut black, &mut white, 100)?,
+        Some(Outcome::Draw)
+    );
+    assert_eq!(board.stones().count(), 9);
+    Ok(())
+}
+
+// Plays the given turns in order, passing once they run out.
+struct Scripted(Vec<(Point, Point)>);
+
+impl MoveGen for Scripted {
+    fn choose(&mut self, _board: &Board, _stone: Stone) -> (Point, Point) {
+        if self.0.is_empty() {
+            return (Point::PASS, Point::PASS);
+        }
+        self.0.remove(0)
+    }
+}
+
+#[test]
+fn test_run_game_pass() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_infinite();
+    let mut black = Scripted(vec![
+        (Point::ORIGIN, Point::PASS),
+        (Point::PASS, Point::new(2, 0)),
+    ]);
+    let mut white = Scripted(vec![
+        (Point::PASS, Point::PASS),
+        (Point::new(1, 0), Point::PASS),
+    ]);
+    assert_eq!(run_game(&mut board, &mut black, &mut white, 4)?, None);
+
+    // A pass ends the turn, and the point after it is not placed.
+    assert_eq!(
+        board.past_record(),
+        [
+            (Point::ORIGIN, Stone::Black),
+            (Point::PASS, Stone::White),
+            (Point::PASS, Stone::Black),
+            (Point::new(1, 0), Stone::White),
+            (Point::PASS, Stone::White),
+        ]
+    );
+    assert!(board.is_empty_at(Point::new(2, 0)));
+    assert_eq!(board.turn().stone, Stone::Black);
+    Ok(())
+}
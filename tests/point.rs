@@ -0,0 +1,217 @@
+use c6::*;
+
+#[test]
+fn test_point_arithmetic() {
+    let p = Point::new(-3, 2);
+    assert_eq!(p + Point::new(5, -4), Point::new(2, -2));
+    assert_eq!(p - Point::new(-3, 2), Point::ORIGIN);
+    assert_eq!(Point::new(1, -1) * 3, Point::new(3, -3));
+    assert_eq!(Point::new(1, -1) * -2, Point::new(-2, 2));
+}
+
+#[test]
+fn test_point_arithmetic_wraps() {
+    let max = Point::new(i32::MAX, i32::MAX);
+    let min = Point::new(i32::MIN, i32::MIN);
+    assert_eq!(max + Point::new(1, 0), Point::new(i32::MIN, i32::MAX));
+    assert_eq!(min - Point::new(0, 1), Point::new(i32::MIN, i32::MAX));
+    assert_eq!(min + Point::new(-1, 1), Point::new(i32::MAX, i32::MIN + 1));
+    assert_eq!(max * 2, Point::new(-2, -2));
+}
+
+#[test]
+fn test_direction_deltas() {
+    let deltas = Direction::ALL.map(Direction::delta);
+    assert_eq!(deltas, [(1, 0), (0, 1), (1, 1), (1, -1)]);
+}
+
+#[test]
+fn test_point_parse() {
+    let p = Point::new(-3, 12);
+    assert_eq!(p.to_string(), "(-3, 12)");
+    assert_eq!("(-3, 12)".parse::<Point>().ok(), Some(p));
+    assert_eq!(" -3 ,12 ".parse::<Point>().ok(), Some(p));
+    assert!("(-3, 12".parse::<Point>().is_err());
+    assert!("(x, 12)".parse::<Point>().is_err());
+}
+
+#[test]
+fn test_point_algebraic() {
+    assert_eq!(Point::from_algebraic("J10"), Some(Point::ORIGIN));
+    assert_eq!(Point::from_algebraic("a1"), Some(Point::new(-9, -9)));
+    assert_eq!(Point::from_algebraic("S19"), Some(Point::new(9, 9)));
+    assert_eq!(Point::from_algebraic("T1"), None);
+    assert_eq!(Point::from_algebraic("A20"), None);
+    assert_eq!(Point::from_algebraic("●1"), None);
+    assert_eq!(Point::new(-9, 9).to_algebraic().as_deref(), Some("A19"));
+    assert_eq!(Point::new(10, 0).to_algebraic(), None);
+}
+
+#[test]
+fn test_stone_parse() {
+    for s in ["black", "Black", "BLACK", "b", "B", "●"] {
+        assert_eq!(s.parse::<Stone>().ok(), Some(Stone::Black), "{s}");
+    }
+    for s in ["white", "White", "WHITE", "w", "W", "○"] {
+        assert_eq!(s.parse::<Stone>().ok(), Some(Stone::White), "{s}");
+    }
+    for s in ["", "red", "bl", " b"] {
+        assert!(s.parse::<Stone>().is_err(), "{s}");
+    }
+    assert_eq!(Stone::Black.to_string(), "Black");
+    assert_eq!(
+        Stone::White.to_string().parse::<Stone>().ok(),
+        Some(Stone::White)
+    );
+}
+
+#[test]
+fn test_by_stone() -> Result<(), Box<dyn std::error::Error>> {
+    let mut board = Board::new_infinite();
+    for (x, stone) in [(0, Stone::Black), (1, Stone::White), (2, Stone::White)] {
+        board.set(Point::new(x, 0), stone)?;
+    }
+
+    let mut counts = ByStone::default();
+    for (_, stone) in board.stones() {
+        counts[stone] += 1;
+    }
+    assert_eq!(counts, ByStone::new(1, 2));
+    assert_eq!(counts[Stone::White], 2);
+    assert_eq!(
+        counts.iter().collect::<Vec<_>>(),
+        [(Stone::Black, &1), (Stone::White, &2)]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_clamp_point() {
+    let far = Point::new(100, -100);
+    assert_eq!(Bounds::Infinite.clamp_point(far), far);
+
+    // The 19 by 19 board spans [-9, 9] and the 4 by 3 one [-2, 1] by [-1, 1].
+    let square = Bounds::Rect(19, 19);
+    assert_eq!(square.clamp_point(far), Point::new(9, -9));
+    assert_eq!(square.clamp_point(Point::new(-10, 3)), Point::new(-9, 3));
+    assert_eq!(square.clamp_point(Point::new(4, 5)), Point::new(4, 5));
+
+    let rect = Bounds::Rect(4, 3);
+    assert_eq!(rect.clamp_point(Point::new(-3, 0)), Point::new(-2, 0));
+    assert_eq!(rect.clamp_point(Point::new(2, 0)), Point::new(1, 0));
+    assert_eq!(rect.clamp_point(Point::new(0, -2)), Point::new(0, -1));
+    assert_eq!(rect.clamp_point(Point::new(0, 2)), Point::new(0, 1));
+    for p in [far, Point::new(i32::MIN, i32::MAX)] {
+        assert!(rect.contains(rect.clamp_point(p)));
+    }
+
+    let limited = Bounds::Limited(5);
+    assert_eq!(limited.clamp_point(far), Point::new(5, -5));
+    let edge = Point::new(i32::MIN, 5);
+    assert_eq!(Bounds::Limited(u32::MAX).clamp_point(edge), edge);
+    assert_eq!(Bounds::Limited(1 << 31).clamp_point(edge), edge);
+    let limited = Bounds::Limited((1 << 31) - 1);
+    assert_eq!(limited.clamp_point(edge), Point::new(-i32::MAX, 5));
+}
+
+#[test]
+fn test_classify() {
+    // The 4 by 3 board spans [-2, 1] by [-1, 1].
+    let rect = Bounds::Rect(4, 3);
+    for (x, y) in [(-2, -1), (1, -1), (-2, 1), (1, 1)] {
+        assert_eq!(rect.classify(Point::new(x, y)), CellKind::Corner);
+    }
+    assert_eq!(rect.classify(Point::new(0, -1)), CellKind::Edge);
+    assert_eq!(rect.classify(Point::new(-2, 0)), CellKind::Edge);
+    assert_eq!(rect.classify(Point::new(-1, 0)), CellKind::Interior);
+    assert_eq!(rect.classify(Point::new(2, 0)), CellKind::Outside);
+
+    // A single point is its own corner.
+    assert_eq!(Bounds::Rect(1, 1).classify(Point::ORIGIN), CellKind::Corner);
+
+    let limited = Bounds::Limited(5);
+    assert_eq!(limited.classify(Point::new(-5, 5)), CellKind::Corner);
+    assert_eq!(limited.classify(Point::new(5, 0)), CellKind::Edge);
+    assert_eq!(limited.classify(Point::new(6, 0)), CellKind::Outside);
+
+    let far = Point::new(i32::MIN, i32::MAX);
+    assert_eq!(Bounds::Infinite.classify(far), CellKind::Interior);
+}
+
+#[test]
+fn test_disk_ring() {
+    let center = Point::new(3, -4);
+    let disk: Vec<Point> = center.disk(2).collect();
+    assert_eq!(disk.len(), 25);
+    assert_eq!(disk[0], Point::new(1, -6));
+    assert_eq!(disk[24], Point::new(5, -2));
+
+    let ring: Vec<Point> = center.ring(2).collect();
+    assert_eq!(ring.len(), 16);
+    assert!(ring.iter().all(|p| disk.contains(p)));
+    assert!(ring
+        .iter()
+        .all(|p| (p.x - 3).abs().max((p.y + 4).abs()) == 2));
+    assert_eq!(center.ring(0).collect::<Vec<_>>(), [center]);
+    assert_eq!(center.disk(0).collect::<Vec<_>>(), [center]);
+
+    // Points past the edges of the coordinate space are skipped.
+    let corner = Point::new(i32::MAX, i32::MIN);
+    assert_eq!(corner.disk(2).count(), 9);
+    assert_eq!(corner.ring(2).count(), 5);
+    assert_eq!(Point::new(i32::MAX, 0).ring(1).count(), 5);
+}
+
+#[test]
+fn test_in_line_with() {
+    let a = Point::new(1, 2);
+
+    // Horizontal, vertical and both diagonals, beyond either end.
+    assert!(Point::new(-5, 2).in_line_with(a, Point::new(3, 2)));
+    assert!(Point::new(1, 9).in_line_with(a, Point::new(1, 0)));
+    assert!(Point::new(4, 5).in_line_with(a, Point::new(0, 1)));
+    assert!(Point::new(-2, 5).in_line_with(a, Point::new(2, 1)));
+    assert!(a.in_line_with(a, Point::new(2, 1)));
+
+    assert!(!Point::new(2, 3).in_line_with(a, Point::new(3, 2)));
+    assert!(!Point::new(3, 4).in_line_with(a, Point::new(-1, 1)));
+    // Not along an axis.
+    assert!(!Point::new(5, 4).in_line_with(a, Point::new(3, 3)));
+
+    // Any axis through a single point.
+    assert!(Point::new(7, -4).in_line_with(a, a));
+    assert!(!Point::new(2, 4).in_line_with(a, a));
+
+    let (min, max) = (
+        Point::new(i32::MIN, i32::MIN),
+        Point::new(i32::MAX, i32::MAX),
+    );
+    assert!(Point::ORIGIN.in_line_with(min, max));
+    assert!(!Point::new(0, 1).in_line_with(min, max));
+}
+
+#[test]
+fn test_sort_points() {
+    let mut points: Vec<Point> = (-20..20)
+        .flat_map(|x| [Point::new(x * 7 % 13, x), Point::new(x, -x * 3)])
+        .collect();
+    sort_points(&mut points);
+    assert!(points.windows(2).all(|w| w[0].index() <= w[1].index()));
+
+    let mut moves = vec![
+        (Point::new(3, 3), Stone::Black),
+        (Point::new(-1, 0), Stone::White),
+        (Point::ORIGIN, Stone::White),
+        (Point::new(-1, 0), Stone::Black),
+    ];
+    sort_moves(&mut moves);
+    assert_eq!(
+        moves,
+        [
+            (Point::ORIGIN, Stone::White),
+            (Point::new(-1, 0), Stone::White),
+            (Point::new(-1, 0), Stone::Black),
+            (Point::new(3, 3), Stone::Black),
+        ]
+    );
+}
@@ -0,0 +1,58 @@
+#![cfg(feature = "rand")]
+
+use c6::*;
+use proptest::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+#[test]
+fn test_play_random_fills_board() {
+    let mut rng = StdRng::seed_from_u64(6);
+    let mut board = Board::new_square(3);
+    assert_eq!(board.play_random(&mut rng, Bounds::Infinite, 100), 9);
+    assert_eq!(board.count_by_color(), (5, 4));
+    assert_eq!(board.play_random(&mut rng, Bounds::Infinite, 1), 0);
+    assert_eq!(board.validate_opening(), Ok(()));
+}
+
+#[test]
+fn test_play_random_fills_limited_area() {
+    let mut rng = StdRng::seed_from_u64(6);
+    let mut board = Board::new(Bounds::Limited(1));
+    assert_eq!(board.play_random(&mut rng, Bounds::Limited(0), 2), 1);
+    assert!(board.is_occupied(Point::ORIGIN));
+    assert_eq!(board.play_random(&mut rng, Bounds::Limited(0), 1), 0);
+    assert_eq!(board.play_random(&mut rng, Bounds::Limited(4), 100), 8);
+    assert_eq!(board.total_count(), 9);
+
+    let mut board = Board::new_square(3);
+    assert_eq!(board.play_random(&mut rng, Bounds::Limited(5), 100), 9);
+    assert_eq!(board.play_random(&mut rng, Bounds::Limited(5), 1), 0);
+}
+
+proptest! {
+    #[test]
+    fn random_board_round_trips(
+        seed: u64,
+        size in 0u32..12,
+        area_size in 0u32..16,
+        moves in 0usize..200,
+        infinite: bool,
+    ) {
+        let bounds = if infinite { Bounds::Infinite } else { Bounds::Rect(size, size) };
+        let mut board = Board::new(bounds);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let placed = board.play_random(&mut rng, Bounds::Rect(area_size, area_size), moves);
+
+        prop_assert!(placed <= moves);
+        prop_assert_eq!(board.total_count(), placed);
+        prop_assert!(board.stones().all(|(point, _)| bounds.contains(point)));
+        prop_assert_eq!(board.validate_opening(), Ok(()));
+        let _ = board.outcome();
+
+        let mut record = Vec::new();
+        board.save_record(&mut record).unwrap();
+        let loaded = Board::load_record(&record[..]).unwrap();
+        prop_assert_eq!(loaded.turn(), board.turn());
+        prop_assert!(loaded == board);
+    }
+}
@@ -1,4 +1,4 @@
-use std::error::Error;
+use std::{error::Error, time::Duration};
 
 use c6::*;
 
@@ -14,11 +14,662 @@ fn test_record_save_load() -> Result<(), Box<dyn Error>> {
         }
     }
 
+    let options = SaveOptions {
+        version: false,
+        ..Default::default()
+    };
     let mut record = Vec::new();
-    board.save_record(&mut record)?;
+    board.save_record_with(&mut record, &options)?;
 
     let board_expected = Board::load_record(RECORD_EXPECTED)?;
     assert_eq!(board, board_expected);
     assert_eq!(record, RECORD_EXPECTED);
     Ok(())
 }
+
+#[test]
+fn test_record_explicit_turn() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_infinite();
+    board.set(Point::ORIGIN, Stone::Black)?;
+    board.set_turn(Turn {
+        stone: Stone::Black,
+        remaining: 2,
+    })?;
+    board.set(Point::new(1, 0), Stone::Black)?;
+
+    let turn = Turn {
+        stone: Stone::Black,
+        remaining: 1,
+    };
+    assert_eq!(board.turn(), turn);
+
+    let mut record = Vec::new();
+    board.save_record(&mut record)?;
+
+    let loaded = Board::load_record(&record[..])?;
+    assert_eq!(loaded, board);
+    assert_eq!(loaded.turn(), turn);
+    Ok(())
+}
+
+#[test]
+fn test_record_line_endings() -> Result<(), Box<dyn Error>> {
+    let board_expected = Board::load_record(RECORD_EXPECTED)?;
+    let record = std::str::from_utf8(RECORD_EXPECTED)?;
+
+    let crlf = record.replace('\n', "\r\n");
+    assert_eq!(Board::load_record(crlf.as_bytes())?, board_expected);
+
+    let no_trailing_newline = record.trim_end();
+    assert_eq!(
+        Board::load_record(no_trailing_newline.as_bytes())?,
+        board_expected
+    );
+
+    let blank_lines = record.replacen("\n=", "\n\n \n=", 1);
+    assert_eq!(Board::load_record(blank_lines.as_bytes())?, board_expected);
+
+    let no_tail = record.replace("-----END CONNECT6 RECORD-----\n", "");
+    assert!(Board::load_record(no_tail.as_bytes()).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_record_save_options() -> Result<(), Box<dyn Error>> {
+    let board = Board::load_record(RECORD_EXPECTED)?;
+
+    let options = SaveOptions {
+        version: false,
+        line_width: 10,
+        crc: false,
+        url_safe: false,
+    };
+    let mut record = Vec::new();
+    board.save_record_with(&mut record, &options)?;
+
+    let record = String::from_utf8(record)?;
+    let mut lines = record.lines().skip(5);
+    assert!(lines.next().is_some_and(|line| line.len() == 8));
+    assert!(!record.contains("Version:"));
+    assert!(!record.contains("\n="));
+    assert_eq!(Board::load_record(record.as_bytes())?, board);
+    Ok(())
+}
+
+#[test]
+fn test_record_resign() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_infinite();
+    board.set(Point::ORIGIN, Stone::Black)?;
+    board.resign(Stone::White);
+    assert_eq!(board.outcome(), Some(Outcome::Resign(Stone::White)));
+
+    let mut record = Vec::new();
+    board.save_record(&mut record)?;
+    let mut loaded = Board::load_record(&record[..])?;
+    assert_eq!(loaded.outcome(), Some(Outcome::Resign(Stone::White)));
+
+    loaded.set(Point::new(1, 0), Stone::White)?;
+    assert_eq!(loaded.outcome(), None);
+    Ok(())
+}
+
+#[test]
+fn test_record_pass() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_infinite();
+    board.set(Point::ORIGIN, Stone::Black)?;
+    board.set(Point::new(1, 0), Stone::White)?;
+    board.pass();
+
+    let mut record = Vec::new();
+    board.save_record(&mut record)?;
+
+    let loaded = Board::load_record(&record[..])?;
+    assert_eq!(loaded, board);
+    assert_eq!(loaded.past_record()[2], (Point::PASS, Stone::White));
+    assert_eq!(loaded.turn().stone, Stone::Black);
+    Ok(())
+}
+
+#[test]
+fn test_verify_record() -> Result<(), Box<dyn Error>> {
+    let summary = Board::verify_record(RECORD_EXPECTED)?;
+    assert_eq!(summary.bounds, Bounds::Rect(19, 19));
+    assert_eq!(summary.count, 19 * 19);
+    assert!(matches!(
+        summary.outcome,
+        Some(Outcome::Win {
+            stone: Stone::Black,
+            ..
+        })
+    ));
+
+    // Two black stones at the origin.
+    let record = "-----BEGIN CONNECT6 RECORD-----
+Board: Infinite
+Count: 2
+
+AAA=
+-----END CONNECT6 RECORD-----
+";
+    let err = Board::verify_record(record.as_bytes()).unwrap_err();
+    assert!(matches!(
+        err,
+        LoadRecordError::Set {
+            index: 1,
+            point: Point::ORIGIN,
+            source: SetError::Occupied
+        }
+    ));
+    assert_eq!(
+        err.to_string(),
+        "move 1 at (0, 0): unable to set on board: occupied"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_record_error_line() {
+    let record = std::str::from_utf8(RECORD_EXPECTED).unwrap();
+
+    let bad_count = record.replace("Count: 361", "Count: 360");
+    let err = Board::load_record(bad_count.as_bytes()).unwrap_err();
+    assert!(matches!(err, LoadRecordError::Data { line: 4, .. }));
+
+    let mut lines: Vec<&str> = record.lines().collect();
+    lines[7] = "not base64!";
+    let bad_base64 = lines.join("\n");
+    let err = Board::load_record(bad_base64.as_bytes()).unwrap_err();
+    assert!(matches!(err, LoadRecordError::Base64 { line: 8, .. }));
+    assert!(err.to_string().starts_with("line 8: "));
+
+    let truncated = &record[..record.find("-----END").unwrap()];
+    let err = Board::load_record(truncated.as_bytes()).unwrap_err();
+    let eof_line = truncated.lines().count() + 1;
+    assert!(matches!(err, LoadRecordError::Syntax { line, .. } if line == eof_line));
+}
+
+#[test]
+fn test_record_without_blank_line() -> Result<(), Box<dyn Error>> {
+    let empty = "-----BEGIN CONNECT6 RECORD-----\n\
+                 Board: Rect(19*19)\n\
+                 Count: 0\n\
+                 -----END CONNECT6 RECORD-----\n";
+    let board = Board::load_record(empty.as_bytes())?;
+    assert_eq!(board.bounds(), Bounds::Rect(19, 19));
+    assert_eq!(board.total_count(), 0);
+
+    let with_blank = empty.replace("Count: 0\n", "Count: 0\n\n");
+    assert_eq!(Board::load_record(with_blank.as_bytes())?, board);
+
+    // The moves may follow the headers directly.
+    let record = std::str::from_utf8(RECORD_EXPECTED)?;
+    let no_blank = record.replacen("\n\n", "\n", 1);
+    assert_eq!(
+        Board::load_record(no_blank.as_bytes())?,
+        Board::load_record(RECORD_EXPECTED)?
+    );
+
+    // Errors in the moves are still reported at their lines.
+    let mut lines: Vec<&str> = no_blank.lines().collect();
+    lines[6] = "not base64!";
+    let err = Board::load_record(lines.join("\n").as_bytes()).unwrap_err();
+    assert!(matches!(err, LoadRecordError::Base64 { line: 7, .. }));
+    Ok(())
+}
+
+#[test]
+fn test_load_records() -> Result<(), Box<dyn Error>> {
+    let first = Board::load_record(RECORD_EXPECTED)?;
+    let mut second = Board::new_square(15);
+    second.set(Point::new(3, -4), Stone::Black)?;
+    second.pass();
+
+    let mut buf = RECORD_EXPECTED.to_vec();
+    buf.push(b'\n');
+    second.save_record(&mut buf)?;
+    let boards = Board::load_records(&buf[..])?;
+    assert_eq!(boards, [first, second]);
+
+    // A single record is still read by itself.
+    assert_eq!(Board::load_record(&buf[..])?, boards[0]);
+    assert_eq!(Board::load_records(&b"\n"[..])?, []);
+
+    // Errors are reported at their lines in the whole file.
+    buf.extend(b"garbage\n");
+    let lines = buf.split(|&b| b == b'\n').count() - 1;
+    let err = Board::load_records(&buf[..]).unwrap_err();
+    assert!(matches!(err, LoadRecordError::Syntax { line, .. } if line == lines));
+    Ok(())
+}
+
+#[test]
+fn test_record_format() -> Result<(), Box<dyn Error>> {
+    let record = std::str::from_utf8(RECORD_EXPECTED)?;
+
+    let future = record.replace("Format: 1", "Format: 99");
+    let err = Board::load_record(future.as_bytes()).unwrap_err();
+    assert!(matches!(
+        err,
+        LoadRecordError::UnsupportedFormat {
+            line: 2,
+            version: 99
+        }
+    ));
+
+    // Records from before the header was introduced are of the first version.
+    let legacy = record.replace("Format: 1\n", "");
+    assert_eq!(
+        Board::load_record(legacy.as_bytes())?,
+        Board::load_record(RECORD_EXPECTED)?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_read_header() -> Result<(), Box<dyn Error>> {
+    let mut reader = RECORD_EXPECTED;
+    let header = Board::read_header(&mut reader)?;
+    assert_eq!(header.bounds, Bounds::Rect(19, 19));
+    assert_eq!(header.count, Some(361));
+    assert_eq!(header.turn, None);
+    assert!(header.metadata.is_empty());
+
+    // The moves are left unread.
+    assert!(reader.starts_with(b"hgzW"));
+
+    let mut record = Vec::new();
+    Board::new_infinite().save_record(&mut record)?;
+    let header = Board::read_header(&record[..])?;
+    assert_eq!(header.metadata[0].0, "Version");
+    Ok(())
+}
+
+#[cfg(feature = "flate2")]
+#[test]
+fn test_record_gz() -> Result<(), Box<dyn Error>> {
+    let board = Board::load_record(RECORD_EXPECTED)?;
+
+    let mut record = Vec::new();
+    board.save_record_gz(&mut record)?;
+    assert!(record.len() < RECORD_EXPECTED.len());
+    assert_eq!(Board::load_record_gz(&record[..])?, board);
+    Ok(())
+}
+
+#[test]
+fn test_record_lenient() -> Result<(), Box<dyn Error>> {
+    let board_expected = Board::load_record(RECORD_EXPECTED)?;
+    let record = std::str::from_utf8(RECORD_EXPECTED)?;
+    let damaged = record
+        .replace("\n=5LMR\n", "\n=AAAA\n")
+        .replace("Count: 361", "Count: 360");
+    assert!(Board::load_record(damaged.as_bytes()).is_err());
+
+    let options = LoadOptions {
+        verify_crc: false,
+        ..Default::default()
+    };
+    let err = Board::load_record_lenient(damaged.as_bytes(), &options).unwrap_err();
+    assert!(matches!(err, LoadRecordError::Data { line: 4, .. }));
+
+    let options = LoadOptions {
+        verify_crc: false,
+        enforce_count: false,
+    };
+    let board = Board::load_record_lenient(damaged.as_bytes(), &options)?;
+    assert_eq!(board, board_expected);
+    Ok(())
+}
+
+#[test]
+fn test_position_string() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_square(19);
+    board.set(Point::ORIGIN, Stone::Black)?;
+    board.set(Point::new(9, -9), Stone::White)?;
+    board.set(Point::new(-1, 0), Stone::White)?;
+
+    let s = board.to_position_string();
+    assert!(s.starts_with("Rect(19*19):Black(2):"));
+    let loaded = Board::from_position_string(&s)?;
+    assert!(loaded.position_equal(&board));
+    assert_eq!(loaded.turn(), board.turn());
+    assert_eq!(loaded.to_position_string(), s);
+
+    // The order of moves is not kept.
+    assert_ne!(loaded, board);
+
+    let board = Board::load_record(RECORD_EXPECTED)?;
+    let s = board.to_position_string();
+    assert!(s.len() < RECORD_EXPECTED.len());
+    assert!(Board::from_position_string(&s)?.position_equal(&board));
+
+    assert_eq!(
+        Board::new_infinite().to_position_string(),
+        "Infinite:Black(1):"
+    );
+    for s in [
+        "",
+        "Infinite:Black(1)",
+        "Rect(1*1):Black(1):Ag",
+        "Infinite:Black(3):",
+    ] {
+        assert!(Board::from_position_string(s).is_err());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_record_segments() -> Result<(), Box<dyn Error>> {
+    let mut board = BoardBuilder::infinite()
+        .black(Point::ORIGIN)
+        .white_pair(Point::new(1, 0), Point::new(1, 1))
+        .build();
+    let mut record = Vec::new();
+    board.save_record(&mut record)?;
+
+    board.set(Point::new(-1, 0), Stone::Black)?;
+    board.set(Point::new(-1, 1), Stone::Black)?;
+    board.append_record(&mut record, 3)?;
+
+    // Take back a move and play on, starting the next segment earlier.
+    board.unset();
+    board.set(Point::new(-2, 0), Stone::Black)?;
+    board.resign(Stone::White);
+    board.append_record(&mut record, 4)?;
+
+    let text = String::from_utf8(record.clone())?;
+    assert_eq!(text.matches("BEGIN CONNECT6 SEGMENT").count(), 2);
+    assert!(text.contains("Format: 2\nVersion: "));
+    assert!(text.contains("From: 4\nCount: 1\nResult: Resign(White)\n"));
+
+    let loaded = Board::load_segmented_record(&record[..])?;
+    assert_eq!(loaded, board);
+    assert_eq!(loaded.outcome(), board.outcome());
+    // A plain load refuses to drop the segments.
+    let err = Board::load_record(&record[..]).unwrap_err();
+    assert!(matches!(
+        err,
+        LoadRecordError::Syntax {
+            msg: "unexpected segment",
+            ..
+        }
+    ));
+
+    let rule_changed = text.replace("From: 4\n", "From: 4\nRule: ExactlySix\n");
+    let err = Board::load_segmented_record(rule_changed.as_bytes()).unwrap_err();
+    assert!(matches!(
+        err,
+        LoadRecordError::Data {
+            msg: "segment changes the rule",
+            ..
+        }
+    ));
+    let rule_kept = text.replace("From: 4\n", "From: 4\nRule: SixOrMore\n");
+    assert_eq!(Board::load_segmented_record(rule_kept.as_bytes())?, board);
+
+    // Segments are written with the given options.
+    let mut segment = Vec::new();
+    let options = SaveOptions {
+        version: false,
+        crc: false,
+        ..SaveOptions::default()
+    };
+    board.append_record_with(&mut segment, 3, &options)?;
+    let segment = String::from_utf8(segment)?;
+    assert!(!segment.contains("Version: "));
+    assert!(!segment.lines().any(|line| line.starts_with('=')));
+
+    let past_end = text.replace("From: 4", "From: 6");
+    let err = Board::load_segmented_record(past_end.as_bytes()).unwrap_err();
+    assert!(matches!(err, LoadRecordError::Data { .. }));
+
+    // Each segment has its own checksum.
+    let crc_line = text.lines().rfind(|line| line.starts_with('=')).unwrap();
+    let bad_crc = text.replace(crc_line, "=AAAA");
+    let err = Board::load_segmented_record(bad_crc.as_bytes()).unwrap_err();
+    assert!(matches!(
+        err,
+        LoadRecordError::Data {
+            msg: "wrong checksum",
+            ..
+        }
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_record_win_rule() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::load_record(RECORD_EXPECTED)?;
+    assert_eq!(board.win_rule(), WinRule::SixOrMore);
+    board.set_win_rule(WinRule::ExactlySix);
+
+    let mut record = Vec::new();
+    board.save_record(&mut record)?;
+    let text = String::from_utf8(record.clone())?;
+    assert!(text.contains("\nFormat: 2\n"));
+    assert!(text.contains("\nRule: ExactlySix\n"));
+    let loaded = Board::load_record(&record[..])?;
+    assert_eq!(loaded.win_rule(), WinRule::ExactlySix);
+    assert_eq!(loaded, board);
+    assert_ne!(loaded, Board::load_record(RECORD_EXPECTED)?);
+    Ok(())
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_square(19);
+    board.set(Point::ORIGIN, Stone::Black)?;
+    board.set(Point::new(1, 0), Stone::White)?;
+    board.pass();
+    board.resign(Stone::Black);
+
+    let json = board.to_json();
+    assert_eq!(
+        json,
+        r#"{"bounds":"Rect(19*19)","moves":[[0,0,"Black"],[1,0,"White"],[-2147483648,-2147483648,"White"]],"result":"Resign(Black)"}"#
+    );
+    let loaded = Board::from_json(&json)?;
+    assert_eq!(loaded, board);
+    assert_eq!(loaded.outcome(), board.outcome());
+
+    let board = Board::load_record(RECORD_EXPECTED)?;
+    assert_eq!(Board::from_json(&board.to_json())?, board);
+
+    assert!(matches!(
+        Board::from_json(r#"{"bounds":"Infinite","moves":[[0,0,"Black"],[0,0,"White"]]}"#),
+        Err(JsonError::Set { index: 1, .. })
+    ));
+    assert!(matches!(
+        Board::from_json(r#"{"moves":[]}"#),
+        Err(JsonError::Field("bounds"))
+    ));
+    assert!(Board::from_json("{").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_record_times() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_infinite();
+    board.set_timed(Point::ORIGIN, Stone::Black, Duration::from_millis(1500))?;
+    board.set(Point::new(1, 0), Stone::White)?;
+    board.set_timed(Point::new(1, 1), Stone::White, Duration::from_secs(100))?;
+    assert_eq!(board.move_time(0), Some(Duration::from_millis(1500)));
+    assert_eq!(board.move_time(1), None);
+
+    let mut record = Vec::new();
+    board.save_record(&mut record)?;
+    let loaded = Board::load_record(&record[..])?;
+    assert_eq!(loaded, board);
+    for i in 0..4 {
+        assert_eq!(loaded.move_time(i), board.move_time(i));
+    }
+
+    // Times are dropped along with their moves.
+    board.unset();
+    board.set(Point::new(2, 2), Stone::White)?;
+    assert_eq!(board.move_time(2), None);
+
+    let text = String::from_utf8(record)?;
+    assert!(text.contains("\nFormat: 2\n"));
+    let times_line = text.lines().find(|l| l.starts_with("Times: ")).unwrap();
+    let untimed = text.replace(&format!("{times_line}\n"), "");
+    let loaded = Board::load_record(untimed.as_bytes())?;
+    assert_eq!(loaded.move_time(0), None);
+
+    let mut short = Board::new_infinite();
+    short.set_timed(Point::ORIGIN, Stone::Black, Duration::ZERO)?;
+    let mut record = Vec::new();
+    short.save_record(&mut record)?;
+    let short_times = String::from_utf8(record)?
+        .lines()
+        .find(|l| l.starts_with("Times: "))
+        .unwrap()
+        .to_owned();
+    let mismatched = text.replace(times_line, &short_times);
+    let err = Board::load_record(mismatched.as_bytes()).unwrap_err();
+    assert!(matches!(
+        err,
+        LoadRecordError::Data {
+            msg: "wrong number of times",
+            ..
+        }
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_record_url_safe() -> Result<(), Box<dyn Error>> {
+    let board = Board::load_record(RECORD_EXPECTED)?;
+    let standard = std::str::from_utf8(RECORD_EXPECTED)?;
+    assert!(standard.contains(['+', '/']));
+
+    let options = SaveOptions {
+        version: false,
+        url_safe: true,
+        ..Default::default()
+    };
+    let mut record = Vec::new();
+    board.save_record_with(&mut record, &options)?;
+    let url_safe = String::from_utf8(record)?;
+    assert!(url_safe.contains(['-', '_']));
+    assert!(!url_safe
+        .lines()
+        .any(|l| !l.starts_with('=') && l.contains(['+', '/'])));
+    assert_eq!(Board::load_record(url_safe.as_bytes())?, board);
+
+    // The checksum is over the decoded moves, so the alphabets can be mixed.
+    let mut lines: Vec<&str> = url_safe.lines().collect();
+    let standard_lines: Vec<&str> = standard.lines().collect();
+    lines[6] = standard_lines[6];
+    assert_ne!(lines[6], url_safe.lines().nth(6).unwrap());
+    let mixed = lines.join("\n");
+    assert_eq!(Board::load_record(mixed.as_bytes())?, board);
+    Ok(())
+}
+
+#[test]
+fn test_record_string() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_square(19);
+    board.set(Point::ORIGIN, Stone::Black)?;
+    board.set(Point::new(1, 0), Stone::White)?;
+    board.pass();
+    board.resign(Stone::Black);
+
+    let record = board.to_string_record();
+    assert_eq!(Board::from_str_record(&record)?, board);
+
+    let mut bytes = Vec::new();
+    board.save_record(&mut bytes)?;
+    assert_eq!(record.as_bytes(), bytes);
+    assert!(Board::from_str_record("").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_compact_record() -> Result<(), Box<dyn Error>> {
+    let (a, b, c) = (Point::ORIGIN, Point::new(1, 0), Point::new(0, 1));
+    let mut board = Board::new_infinite();
+    board.set(a, Stone::Black)?;
+    board.set(c, Stone::White)?;
+    board.set(Point::new(5, 5), Stone::White)?;
+    board.unset();
+    board.set(b, Stone::White)?;
+    board.set(Point::new(2, 2), Stone::Black)?;
+    board.unset();
+
+    let mut expected = Board::new_infinite();
+    expected.set(a, Stone::Black)?;
+    expected.set(c, Stone::White)?;
+    expected.set(b, Stone::White)?;
+    board.compact_record(false);
+    assert_eq!(board.to_string_record(), expected.to_string_record());
+    assert!(board.jump(4).is_err());
+
+    // The point `b` comes before `c` in canonical order.
+    let mut sorted = Board::new_infinite();
+    sorted.set(a, Stone::Black)?;
+    sorted.set(b, Stone::White)?;
+    sorted.set(c, Stone::White)?;
+    board.compact_record(true);
+    assert_eq!(board.past_record(), sorted.past_record());
+    assert_eq!(board.to_string_record(), sorted.to_string_record());
+    Ok(())
+}
+
+#[test]
+fn test_record_lines() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_infinite();
+    board.set(Point::ORIGIN, Stone::Black)?;
+    board.set_timed(Point::new(1, 0), Stone::White, Duration::from_millis(1500))?;
+    board.set(Point::new(20, -3), Stone::White)?;
+    board.pass();
+
+    let mut lines = Vec::new();
+    board.save_record_lines(&mut lines)?;
+    let text = String::from_utf8(lines)?;
+    assert_eq!(
+        text,
+        "-----BEGIN CONNECT6 MOVES-----\n\
+         Format: 2\n\
+         Board: Infinite\n\
+         \n\
+         Black J10\n\
+         White K10 1500ms\n\
+         White (20, -3)\n\
+         Black pass\n\
+         -----END CONNECT6 MOVES-----\n"
+    );
+    let loaded = Board::load_record_lines(text.as_bytes())?;
+    assert_eq!(loaded, board);
+    assert_eq!(loaded.move_time(1), Some(Duration::from_millis(1500)));
+
+    // Making a move adds exactly one line.
+    board.set(Point::new(-1, -1), Stone::White)?;
+    let mut lines = Vec::new();
+    board.save_record_lines(&mut lines)?;
+    let new_text = String::from_utf8(lines)?;
+    let (old, new): (Vec<_>, Vec<_>) = (text.lines().collect(), new_text.lines().collect());
+    assert_eq!(new.len(), old.len() + 1);
+    assert_eq!(new[..8], old[..8]);
+    assert_eq!(new[8], "White I9");
+    assert_eq!(new[9..], old[8..]);
+
+    // Blank lines between the moves are skipped.
+    let spaced = new_text.replace("\nBlack pass\n", "\n\nBlack pass\n  \n");
+    assert_eq!(Board::load_record_lines(spaced.as_bytes())?, board);
+
+    // Untimed records on the default rule are still of the first version.
+    let mut untimed = Board::new_infinite();
+    untimed.set(Point::ORIGIN, Stone::Black)?;
+    let mut lines = Vec::new();
+    untimed.save_record_lines(&mut lines)?;
+    assert!(String::from_utf8(lines)?.contains("\nFormat: 1\n"));
+
+    let bad = "-----BEGIN CONNECT6 MOVES-----\n\nBlack Z99\n";
+    assert!(matches!(
+        Board::load_record_lines(bad.as_bytes()),
+        Err(LoadRecordError::Syntax { line: 3, .. })
+    ));
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use std::error::Error;
+
+use c6::*;
+
+const LOG: &str = "\
+    game started
+    White moved (K10, K11)
+    Black moved (H9, G8)
+    ";
+
+#[test]
+fn test_from_server_log() -> Result<(), Box<dyn Error>> {
+    let board = Board::from_server_log(LOG.as_bytes())?;
+    assert_eq!(board.bounds(), Bounds::Rect(19, 19));
+    assert_eq!(board.total_count(), 5);
+    assert_eq!(board.get(Point::ORIGIN), Some(Stone::Black));
+    assert_eq!(board.get(Point::new(1, 1)), Some(Stone::White));
+    assert_eq!(board.get(Point::new(-2, -1)), Some(Stone::Black));
+
+    // A log with its own opening move gets no extra stone.
+    let log = format!("Black moved (J10)\n{LOG}");
+    for auto_first in [true, false] {
+        let options = ServerLogOptions {
+            auto_first,
+            ..ServerLogOptions::default()
+        };
+        let board = Board::from_server_log_with(log.as_bytes(), &options)?;
+        assert_eq!(board.past_record()[0], (Point::ORIGIN, Stone::Black));
+        assert_eq!(board.total_count(), 5);
+    }
+
+    // The offset sets the size of the board.
+    let options = ServerLogOptions {
+        offset: 3,
+        auto_first: true,
+    };
+    let board = Board::from_server_log_with("White moved (A1, G7)".as_bytes(), &options)?;
+    assert_eq!(board.bounds(), Bounds::Rect(7, 7));
+    assert_eq!(board.get(Point::new(-3, -3)), Some(Stone::White));
+    assert_eq!(board.get(Point::new(3, 3)), Some(Stone::White));
+
+    // Lines may be prefixed, as with a timestamp.
+    let log = "[12:00:01] game started\n\
+               [12:00:05] White moved (K10, K11)\n\
+               [12:00:09] Black moved (H9, G8)\n";
+    assert_eq!(
+        Board::from_server_log(log.as_bytes())?,
+        Board::from_server_log(LOG.as_bytes())?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_from_server_log_errors() {
+    let err = |log: &str| Board::from_server_log(log.as_bytes()).unwrap_err();
+
+    let log = format!("{LOG}\nWhite moved (K12, 13)\n");
+    assert!(matches!(
+        err(&log),
+        LoadRecordError::Syntax {
+            line: 5,
+            msg: "malformed coordinates"
+        }
+    ));
+    assert!(matches!(
+        err("Black moved (J10, K10, L10)"),
+        LoadRecordError::Syntax { line: 1, .. }
+    ));
+    assert!(matches!(
+        err("Red moved (J10)"),
+        LoadRecordError::Syntax {
+            line: 1,
+            msg: "unknown player"
+        }
+    ));
+    assert!(matches!(
+        err("White moved (A1, A1)"),
+        LoadRecordError::Data {
+            line: 1,
+            msg: "point is occupied"
+        }
+    ));
+    let log = format!("{LOG}White moved (Z1, K12)\n");
+    assert!(matches!(
+        err(&log),
+        LoadRecordError::Data {
+            line: 4,
+            msg: "point is out of bounds"
+        }
+    ));
+
+    // Without the opening move, the second turn is short a stone.
+    let options = ServerLogOptions {
+        auto_first: false,
+        ..ServerLogOptions::default()
+    };
+    let err = Board::from_server_log_with(LOG.as_bytes(), &options).unwrap_err();
+    assert!(matches!(err, LoadRecordError::Data { line: 2, .. }));
+}
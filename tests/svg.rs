@@ -0,0 +1,48 @@
+use std::error::Error;
+
+use c6::*;
+
+#[test]
+fn test_render_svg() -> Result<(), Box<dyn Error>> {
+    let mut board = Board::new_square(19);
+    board.set(Point::ORIGIN, Stone::Black)?;
+    board.set(Point::new(1, 0), Stone::White)?;
+    board.set(Point::new(-1, 2), Stone::White)?;
+    board.set(Point::new(8, 8), Stone::Black)?;
+
+    // The occupied bounds contain the four stones and the four star points
+    // at (0, 0), (6, 0), (0, 6) and (6, 6).
+    let svg = board.render_svg(None)?;
+    assert!(svg.starts_with("<svg"));
+    assert_eq!(svg.matches("<circle").count(), 8);
+    assert_eq!(svg.matches(r#"fill="white""#).count(), 2);
+
+    // A window covering the whole board shows all nine star points.
+    let window = (Point::new(-9, -9), Point::new(9, 9));
+    let svg = board.render_svg(Some(window))?;
+    assert_eq!(svg.matches("<circle").count(), 13);
+    assert_eq!(svg.matches("<line").count(), 38);
+
+    // An infinite board has star points on every sixth line, as in the app.
+    let mut board = Board::new_infinite();
+    board.set(Point::ORIGIN, Stone::Black)?;
+    board.set(Point::new(12, 7), Stone::White)?;
+    let svg = board.render_svg(None)?;
+    assert_eq!(svg.matches("<circle").count(), 2 + 3 * 2);
+
+    // Stones far apart make the occupied bounds too large to render.
+    board.set(Point::new(-5000, 0), Stone::White)?;
+    assert_eq!(
+        board.render_svg(None),
+        Err(SvgSizeError {
+            width: 5013,
+            height: 8
+        })
+    );
+    let window = (Point::new(-3, -3), Point::new(3, 3));
+    assert_eq!(
+        board.render_svg(Some(window))?.matches("<circle").count(),
+        2
+    );
+    Ok(())
+}